@@ -0,0 +1,143 @@
+use std::time::Duration;
+
+use advent_of_code_2021::{
+    helpers::{
+        benchmarking::{self, BenchmarkRegistry},
+        input_generation,
+    },
+    register_reworked_solution_benchmarks, register_solution_benchmarks, reworked_solutions,
+    solutions,
+};
+use criterion::{
+    criterion_group, criterion_main, measurement::WallTime, BenchmarkGroup, BenchmarkId, Criterion,
+};
+
+// Brute force solutions that take minutes per iteration.
+// Only their parsing is benchmarked here, their tasks run on a reduced workload in `reduced_inputs`.
+const PARSE_ONLY_DAYS: &[&str] = &["day24"];
+
+// The number of model number digits the reduced day 24 workload checks, out of 14
+const DAY24_REDUCED_DIGITS: usize = 4;
+
+// Solutions that take more than 100 ms per iteration
+const SLOW_DAYS: &[&str] = &["day15", "day23", "day25"];
+
+struct CriterionRegistry<'a> {
+    criterion: &'a mut Criterion,
+}
+
+struct CriterionGroup<'a> {
+    group: BenchmarkGroup<'a, WallTime>,
+    parse_only: bool,
+}
+
+impl BenchmarkRegistry for CriterionRegistry<'_> {
+    type Group<'a>
+        = CriterionGroup<'a>
+    where
+        Self: 'a;
+
+    fn group(&mut self, group: &str, day: &str) -> CriterionGroup<'_> {
+        let mut benchmark_group = self.criterion.benchmark_group(format!("{}/{}", group, day));
+        if SLOW_DAYS.contains(&day) {
+            benchmark_group.sample_size(10);
+        }
+        CriterionGroup {
+            group: benchmark_group,
+            parse_only: PARSE_ONLY_DAYS.contains(&day),
+        }
+    }
+}
+
+impl benchmarking::BenchmarkGroup for CriterionGroup<'_> {
+    fn bench<T, F>(&mut self, name: &str, mut routine: F)
+    where
+        F: FnMut() -> T,
+    {
+        if self.parse_only && name != "parse" {
+            return;
+        }
+        self.group.bench_function(name, |b| b.iter(&mut routine));
+    }
+}
+
+fn complex_inputs(c: &mut Criterion) {
+    let mut registry = CriterionRegistry { criterion: c };
+    register_solution_benchmarks(&mut registry);
+    register_reworked_solution_benchmarks(&mut registry);
+}
+
+fn scaled_inputs(c: &mut Criterion) {
+    let mut group = c.benchmark_group("scaled/day15");
+    group.sample_size(10);
+    for size in [25, 50, 100, 200] {
+        let input_data = solutions::day15::parse_input(&input_generation::day15(size, 0));
+        group.bench_with_input(BenchmarkId::new("task1", size), &input_data, |b, input| {
+            b.iter(|| solutions::day15::task1(input))
+        });
+    }
+    group.finish();
+
+    let mut group = c.benchmark_group("scaled/day20");
+    group.sample_size(10);
+    for size in [25, 50, 100, 200] {
+        let input_data = solutions::day20::parse_input(&input_generation::day20(size, 0));
+        group.bench_with_input(BenchmarkId::new("task2", size), &input_data, |b, input| {
+            b.iter(|| solutions::day20::task2(input))
+        });
+    }
+    group.finish();
+
+    let mut group = c.benchmark_group("scaled/day22");
+    group.sample_size(10);
+    group.measurement_time(Duration::from_secs(10));
+    for size in [25, 50, 100, 200] {
+        let input_str = input_generation::day22(size, 0);
+
+        let input_data = solutions::day22::parse_input(&input_str);
+        group.bench_with_input(BenchmarkId::new("task2", size), &input_data, |b, input| {
+            b.iter(|| solutions::day22::task2(input))
+        });
+
+        let input_data = reworked_solutions::day22::parse_input(&input_str);
+        group.bench_with_input(
+            BenchmarkId::new("reworked_task2", size),
+            &input_data,
+            |b, input| b.iter(|| reworked_solutions::day22::task2(input)),
+        );
+    }
+    group.finish();
+}
+
+/// Runs the day 24 brute force on the first few digits of the MONAD program only.
+fn reduced_inputs(c: &mut Criterion) {
+    let data = match benchmarking::load_benchmark_input("day24") {
+        Some(data) => data,
+        None => return,
+    };
+    let monad = solutions::day24::parse_input(&data);
+    // Every digit is processed by a block of instructions that starts with `inp`
+    let reduced_len = monad
+        .iter()
+        .enumerate()
+        .filter(|(_, instruction)| matches!(instruction, solutions::day24::Instruction::Inp(_)))
+        .nth(DAY24_REDUCED_DIGITS)
+        .map(|(pos, _)| pos)
+        .unwrap_or(monad.len());
+    // Accept all model numbers, as the tasks require at least one valid one
+    let mut reduced_monad = monad[..reduced_len].to_vec();
+    reduced_monad.extend(solutions::day24::parse_input("mul z 0"));
+
+    let mut group = c.benchmark_group("reduced/day24");
+    group.sample_size(10);
+    group.bench_function("task1", |b| {
+        b.iter(|| solutions::day24::task1(&reduced_monad))
+    });
+    group.bench_function("task2", |b| {
+        b.iter(|| solutions::day24::task2(&reduced_monad))
+    });
+    group.finish();
+}
+
+criterion_group!(benches, complex_inputs, scaled_inputs, reduced_inputs);
+criterion_main!(benches);
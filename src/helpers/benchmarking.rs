@@ -0,0 +1,37 @@
+use std::{fs, path::PathBuf, time::Duration};
+
+use super::input_normalization::normalize_input;
+
+pub struct SolutionOutput {
+    pub result: String,
+    pub parse_time: Duration,
+    pub calculation_time: Duration,
+}
+
+/// Receives the benchmarks generated by the `solutions!` and
+/// `reworked_solutions!` macros, grouped by day.
+pub trait BenchmarkRegistry {
+    type Group<'a>: BenchmarkGroup
+    where
+        Self: 'a;
+
+    /// Starts the benchmarks of one day, which get added until the group is dropped.
+    fn group(&mut self, group: &str, day: &str) -> Self::Group<'_>;
+}
+
+/// The benchmarks of a single day, e.g. parsing and both tasks.
+pub trait BenchmarkGroup {
+    fn bench<T, F>(&mut self, name: &str, routine: F)
+    where
+        F: FnMut() -> T;
+}
+
+pub fn load_benchmark_input(day: &str) -> Option<String> {
+    let input_file = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("input_data")
+        .join(format!("{}_complex.txt", day));
+
+    fs::read_to_string(input_file)
+        .ok()
+        .map(|data| normalize_input(&data).0)
+}
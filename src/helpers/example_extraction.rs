@@ -0,0 +1,164 @@
+use std::{
+    fmt::Write,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{Context, Result};
+use lazy_static::lazy_static;
+use regex::Regex;
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct PuzzleExamples {
+    /// The contents of all `<pre><code>` blocks, in order of appearance
+    pub examples: Vec<String>,
+    /// The last emphasized `<code>` of every puzzle part, which is usually the example answer
+    pub answers: Vec<Option<String>>,
+}
+
+fn decode_html(text: &str) -> String {
+    lazy_static! {
+        static ref TAG: Regex = Regex::new(r"<[^>]*>").unwrap();
+    }
+    TAG.replace_all(text, "")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&amp;", "&")
+}
+
+pub fn extract_examples(html: &str) -> PuzzleExamples {
+    lazy_static! {
+        static ref ARTICLE: Regex =
+            Regex::new(r#"(?s)<article class="day-desc">(.*?)</article>"#).unwrap();
+        static ref EXAMPLE: Regex = Regex::new(r"(?s)<pre><code>(.*?)</code></pre>").unwrap();
+        static ref ANSWER: Regex = Regex::new(r"(?s)<code><em>(.*?)</em></code>").unwrap();
+    }
+
+    let examples = EXAMPLE
+        .captures_iter(html)
+        .map(|captures| decode_html(&captures[1]))
+        .collect();
+
+    let answers = ARTICLE
+        .captures_iter(html)
+        .map(|article| {
+            ANSWER
+                .captures_iter(&article[1])
+                .last()
+                .map(|captures| decode_html(&captures[1]))
+        })
+        .collect();
+
+    PuzzleExamples { examples, answers }
+}
+
+fn format_answer(answer: &str) -> String {
+    if answer.parse::<i64>().is_ok() {
+        answer.to_string()
+    } else {
+        format!("{:?}", answer)
+    }
+}
+
+/// Writes the examples of a saved puzzle description page to `<target_dir>/dayXX_simpleN.txt`.
+///
+/// Existing files are never overwritten. Returns the written files and a
+/// suggested `aoc_tests!` table, assuming the first example belongs to all answers.
+pub fn write_examples(
+    day: u8,
+    html_file: &Path,
+    target_dir: &Path,
+) -> Result<(Vec<PathBuf>, String)> {
+    let html = fs::read_to_string(html_file)
+        .with_context(|| format!("Unable to open '{}'", html_file.display()))?;
+    let puzzle_examples = extract_examples(&html);
+
+    let mut written = vec![];
+    for (id, example) in puzzle_examples.examples.iter().enumerate() {
+        let path = target_dir.join(format!("day{:0>2}_simple{}.txt", day, id + 1));
+        if path.exists() {
+            println!("Skipping '{}', it already exists.", path.display());
+            continue;
+        }
+        fs::write(&path, example)
+            .with_context(|| format!("Unable to write '{}'", path.display()))?;
+        written.push(path);
+    }
+
+    let mut test_table = String::new();
+    writeln!(test_table, "crate::aoc_tests! {{")?;
+    for (task_id, answer) in puzzle_examples.answers.iter().enumerate() {
+        if task_id > 0 {
+            writeln!(test_table, ",")?;
+        }
+        writeln!(test_table, "    task{}: {{", task_id + 1)?;
+        if let (Some(answer), false) = (answer, puzzle_examples.examples.is_empty()) {
+            writeln!(test_table, "        simple1 => {},", format_answer(answer))?;
+        }
+        write!(test_table, "    }}")?;
+    }
+    writeln!(test_table)?;
+    write!(test_table, "}}")?;
+
+    Ok((written, test_table))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::helpers::test_dir::TestDir;
+
+    const PUZZLE_HTML: &str = r#"<main>
+<article class="day-desc"><h2>--- Day 1: Sonar Sweep ---</h2>
+<p>For example, suppose you had the following report:</p>
+<pre><code>199
+200
+<em>208</em>
+</code></pre>
+<p>In this example, there are <code><em>7</em></code> measurements &amp; more.</p>
+<pre><code>a -&gt; b
+</code></pre>
+</article>
+<p>Your puzzle answer was <code>1655</code>.</p>
+<article class="day-desc"><h2 id="part2">--- Part Two ---</h2>
+<p>The answer is <code><em>5</em></code>, not <code><em>7</em></code>. Actually, it is <code><em>x&lt;5</em></code>.</p>
+</article>
+</main>"#;
+
+    #[test]
+    fn works() {
+        assert_eq!(
+            extract_examples(PUZZLE_HTML),
+            PuzzleExamples {
+                examples: vec!["199\n200\n208\n".to_string(), "a -> b\n".to_string()],
+                answers: vec![Some("7".to_string()), Some("x<5".to_string())],
+            }
+        );
+    }
+
+    #[test]
+    fn writes_files_and_test_table() {
+        let dir = TestDir::new("examples");
+        let html_file = dir.join("day01.html");
+        fs::write(&html_file, PUZZLE_HTML).unwrap();
+        fs::write(dir.join("day01_simple2.txt"), "existing").unwrap();
+
+        let (written, test_table) = write_examples(1, &html_file, dir.path()).unwrap();
+
+        assert_eq!(written, vec![dir.join("day01_simple1.txt")]);
+        assert_eq!(
+            fs::read_to_string(dir.join("day01_simple1.txt")).unwrap(),
+            "199\n200\n208\n"
+        );
+        assert_eq!(
+            fs::read_to_string(dir.join("day01_simple2.txt")).unwrap(),
+            "existing"
+        );
+        assert_eq!(
+            test_table,
+            "crate::aoc_tests! {\n    task1: {\n        simple1 => 7,\n    },\n    task2: {\n        simple1 => \"x<5\",\n    }\n}"
+        );
+    }
+}
@@ -0,0 +1,178 @@
+//! Generators for puzzle inputs of arbitrary size.
+//!
+//! They produce inputs in the same format as the real ones, so they can be
+//! fed through the normal `parse_input` functions.
+
+use std::fmt::Write;
+
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+pub struct Generator {
+    /// What the size parameter of `generate` means
+    pub size_description: &'static str,
+    /// The smallest size that makes sense for a scaling analysis
+    pub start_size: usize,
+    pub generate: fn(usize, u64) -> String,
+}
+
+pub fn generator(day: u8) -> Option<Generator> {
+    let (size_description, start_size, generate): (_, _, fn(usize, u64) -> String) = match day {
+        5 => ("grid width, number of lines", 64, day05),
+        15 => ("map width", 16, day15),
+        20 => ("image width", 16, day20),
+        22 => ("number of cuboids", 25, day22),
+        25 => ("seafloor width", 16, day25),
+        _ => return None,
+    };
+
+    Some(Generator {
+        size_description,
+        start_size,
+        generate,
+    })
+}
+
+pub fn day05(size: usize, seed: u64) -> String {
+    let mut rng = StdRng::seed_from_u64(seed);
+
+    let mut result = String::new();
+    for _ in 0..size {
+        let start = (rng.gen_range(0..size), rng.gen_range(0..size));
+        let length = rng.gen_range(0..size) as i64;
+        // Horizontal, vertical or diagonal
+        let direction = [(1, 0), (0, 1), (1, 1), (1, -1)][rng.gen_range(0..4)];
+        let end = (
+            (start.0 as i64 + direction.0 * length).clamp(0, size as i64 - 1),
+            (start.1 as i64 + direction.1 * length).clamp(0, size as i64 - 1),
+        );
+        // Shorten diagonals that got clamped, they have to stay at 45 degrees
+        let length = std::cmp::min(
+            (end.0 - start.0 as i64).abs(),
+            (end.1 - start.1 as i64).abs(),
+        );
+        let end = match direction {
+            (1, 0) | (0, 1) => end,
+            _ => (
+                start.0 as i64 + direction.0 * length,
+                start.1 as i64 + direction.1 * length,
+            ),
+        };
+
+        writeln!(result, "{},{} -> {},{}", start.0, start.1, end.0, end.1).unwrap();
+    }
+    result
+}
+
+pub fn day15(size: usize, seed: u64) -> String {
+    let mut rng = StdRng::seed_from_u64(seed);
+
+    let mut result = String::new();
+    for _ in 0..size {
+        for _ in 0..size {
+            result.push(char::from(b'0' + rng.gen_range(1..=9)));
+        }
+        result.push('\n');
+    }
+    result
+}
+
+pub fn day20(size: usize, seed: u64) -> String {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let pixel = |rng: &mut StdRng| if rng.gen_bool(0.5) { '#' } else { '.' };
+
+    // Like in the real inputs, the infinite background flips every iteration
+    let mut result = (0..512)
+        .map(|id| match id {
+            0 => '#',
+            511 => '.',
+            _ => pixel(&mut rng),
+        })
+        .collect::<String>();
+    result.push_str("\n\n");
+
+    for _ in 0..size {
+        for _ in 0..size {
+            result.push(pixel(&mut rng));
+        }
+        result.push('\n');
+    }
+    result
+}
+
+pub fn day22(num_cuboids: usize, seed: u64) -> String {
+    let mut rng = StdRng::seed_from_u64(seed);
+
+    // Like in the real inputs, about one in 21 cuboids is inside the initialization area,
+    // where they overlap heavily. They dominate the solver's work.
+    let num_initialization_cuboids = std::cmp::max(1, num_cuboids * 20 / 420);
+
+    let mut result = String::new();
+    for id in 0..num_cuboids {
+        let on = id == 0 || rng.gen_bool(0.7);
+
+        // The distributions of centers and sizes of the real inputs
+        let range = |rng: &mut StdRng| {
+            let (center, size) = match (id < num_initialization_cuboids, on) {
+                (true, true) => (rng.gen_range(-25..25), rng.gen_range(45..55)),
+                (true, false) => (rng.gen_range(-45..45), rng.gen_range(9..20)),
+                (false, _) => (
+                    rng.gen_range(-85000..85000),
+                    rng.gen_range(0..20000) + rng.gen_range(0..20000),
+                ),
+            };
+            (center - size / 2, center + size / 2)
+        };
+        let (x, y, z) = (range(&mut rng), range(&mut rng), range(&mut rng));
+        let state = if on { "on" } else { "off" };
+
+        writeln!(
+            result,
+            "{} x={}..{},y={}..{},z={}..{}",
+            state, x.0, x.1, y.0, y.1, z.0, z.1
+        )
+        .unwrap();
+    }
+    result
+}
+
+pub fn day25(size: usize, seed: u64) -> String {
+    let mut rng = StdRng::seed_from_u64(seed);
+
+    let mut result = String::new();
+    for _ in 0..size {
+        for _ in 0..size {
+            result.push(match rng.gen_range(0..10) {
+                0..=2 => '>',
+                3..=5 => 'v',
+                _ => '.',
+            });
+        }
+        result.push('\n');
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn works() {
+        assert_eq!(day15(12, 42), day15(12, 42));
+
+        let map = crate::solutions::day15::parse_input(&day15(12, 42));
+        assert_eq!(map.dim(), (12, 12));
+
+        let image = crate::solutions::day20::parse_input(&day20(12, 42));
+        assert!(crate::solutions::day20::task1(&image) > 0);
+
+        let cuboids = crate::reworked_solutions::day22::parse_input(&day22(30, 42));
+        assert_eq!(cuboids.len(), 30);
+
+        let vent_lines = crate::solutions::day05::parse_input(&day05(50, 42));
+        assert!(crate::solutions::day05::task2(&vent_lines) > 0);
+
+        let seafloor = crate::solutions::day25::parse_input(&day25(12, 42));
+        assert!(crate::solutions::day25::task1(&seafloor) > 0);
+    }
+}
@@ -0,0 +1,354 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use anyhow::{anyhow, bail, Context, Result};
+
+pub const SESSION_ENV_VAR: &str = "AOC_SESSION";
+
+const LAST_FETCH_FILE: &str = ".last_fetch";
+
+pub fn input_file_name(day: u8) -> String {
+    format!("day{:0>2}_complex.txt", day)
+}
+
+/// Something that is able to download the puzzle input of a given day.
+pub trait FetchBackend {
+    fn fetch(&self, year: u16, day: u8) -> Result<String>;
+}
+
+pub struct HttpBackend {
+    base_url: String,
+    /// Only required once something actually gets fetched
+    session: Option<String>,
+}
+
+impl HttpBackend {
+    pub fn new(base_url: impl Into<String>, session: impl Into<String>) -> Self {
+        Self {
+            base_url: base_url.into(),
+            session: Some(session.into()),
+        }
+    }
+
+    pub fn from_env() -> Self {
+        Self {
+            base_url: "https://adventofcode.com".to_string(),
+            session: std::env::var(SESSION_ENV_VAR)
+                .ok()
+                .map(|session| session.trim().to_string()),
+        }
+    }
+}
+
+impl FetchBackend for HttpBackend {
+    fn fetch(&self, year: u16, day: u8) -> Result<String> {
+        let session = self.session.as_ref().ok_or_else(|| {
+            anyhow!(
+                "No session token found. Set '{}' to the value of your adventofcode.com session cookie.",
+                SESSION_ENV_VAR
+            )
+        })?;
+
+        let url = format!("{}/{}/day/{}/input", self.base_url, year, day);
+        let response = ureq::get(&url)
+            .set("Cookie", &format!("session={}", session))
+            .set(
+                "User-Agent",
+                "github.com/Finomnis/AdventOfCode2021 input manager",
+            )
+            .call()
+            .map_err(|err| anyhow!("Unable to fetch '{}': {}", url, err))?;
+
+        response
+            .into_string()
+            .with_context(|| format!("Unable to read response of '{}'", url))
+    }
+}
+
+/// Resolves puzzle inputs to files in a local cache directory.
+///
+/// Inputs are only downloaded if they are not cached yet, and downloads
+/// are spaced at least `min_fetch_interval` apart, even across multiple runs.
+pub struct InputManager<B> {
+    year: u16,
+    cache_dir: PathBuf,
+    backend: B,
+    min_fetch_interval: Duration,
+}
+
+impl<B: FetchBackend> InputManager<B> {
+    pub fn new(year: u16, cache_dir: impl Into<PathBuf>, backend: B) -> Self {
+        Self {
+            year,
+            cache_dir: cache_dir.into(),
+            backend,
+            min_fetch_interval: Duration::from_secs(10),
+        }
+    }
+
+    pub fn with_min_fetch_interval(mut self, min_fetch_interval: Duration) -> Self {
+        self.min_fetch_interval = min_fetch_interval;
+        self
+    }
+
+    pub fn cached_path(&self, day: u8) -> PathBuf {
+        self.cache_dir.join(input_file_name(day))
+    }
+
+    pub fn get(&self, day: u8) -> Result<PathBuf> {
+        if !(1..=25).contains(&day) {
+            bail!("Invalid day: {}", day);
+        }
+
+        let path = self.cached_path(day);
+        if path.exists() {
+            return Ok(path);
+        }
+
+        self.wait_for_rate_limit()?;
+        println!("Fetching input for {} day {} ...", self.year, day);
+        let fetch_result = self.backend.fetch(self.year, day);
+        self.record_fetch()?;
+        let data = fetch_result?;
+
+        if data.trim().is_empty() {
+            bail!("Received empty input for {} day {}", self.year, day);
+        }
+
+        fs::create_dir_all(&self.cache_dir)
+            .with_context(|| format!("Unable to create '{}'", self.cache_dir.display()))?;
+        write_atomically(&path, &data)?;
+
+        Ok(path)
+    }
+
+    fn last_fetch_path(&self) -> PathBuf {
+        self.cache_dir.join(LAST_FETCH_FILE)
+    }
+
+    fn wait_for_rate_limit(&self) -> Result<()> {
+        let last_fetch = match fs::read_to_string(self.last_fetch_path()) {
+            Ok(content) => content
+                .trim()
+                .parse::<u64>()
+                .map(|millis| UNIX_EPOCH + Duration::from_millis(millis))
+                .ok(),
+            Err(_) => None,
+        };
+
+        if let Some(last_fetch) = last_fetch {
+            let elapsed = SystemTime::now()
+                .duration_since(last_fetch)
+                .unwrap_or_default();
+            if let Some(remaining) = self.min_fetch_interval.checked_sub(elapsed) {
+                println!(
+                    "Rate limit: waiting {:.1} s before fetching ...",
+                    remaining.as_secs_f64()
+                );
+                std::thread::sleep(remaining);
+            }
+        }
+
+        Ok(())
+    }
+
+    fn record_fetch(&self) -> Result<()> {
+        fs::create_dir_all(&self.cache_dir)
+            .with_context(|| format!("Unable to create '{}'", self.cache_dir.display()))?;
+        // Rounded up, so the rate limit can't be undershot by the truncation
+        let now = SystemTime::now().duration_since(UNIX_EPOCH)?;
+        let now_millis = now.as_micros().div_ceil(1000);
+        fs::write(self.last_fetch_path(), now_millis.to_string())
+            .with_context(|| format!("Unable to write '{}'", self.last_fetch_path().display()))
+    }
+}
+
+fn write_atomically(path: &Path, data: &str) -> Result<()> {
+    let temp_path = path.with_extension("part");
+    fs::write(&temp_path, data)
+        .with_context(|| format!("Unable to write '{}'", temp_path.display()))?;
+    fs::rename(&temp_path, path).with_context(|| format!("Unable to write '{}'", path.display()))
+}
+
+/// A minimal HTTP server that serves puzzle inputs from memory.
+#[cfg(test)]
+pub mod mock_server {
+    use std::{
+        collections::HashMap,
+        io::{BufRead, BufReader, Write},
+        net::{TcpListener, TcpStream},
+        sync::{
+            atomic::{AtomicUsize, Ordering},
+            Arc,
+        },
+    };
+
+    pub struct MockServer {
+        address: String,
+        requests: Arc<AtomicUsize>,
+    }
+
+    impl MockServer {
+        pub fn start(session: &str, inputs: HashMap<String, String>) -> Self {
+            let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+            let address = format!("http://{}", listener.local_addr().unwrap());
+            let requests = Arc::new(AtomicUsize::new(0));
+
+            let session = format!("session={}", session);
+            let requests_counter = Arc::clone(&requests);
+            std::thread::spawn(move || {
+                for stream in listener.incoming().flatten() {
+                    requests_counter.fetch_add(1, Ordering::SeqCst);
+                    handle_request(stream, &session, &inputs);
+                }
+            });
+
+            Self { address, requests }
+        }
+
+        pub fn url(&self) -> &str {
+            &self.address
+        }
+
+        pub fn num_requests(&self) -> usize {
+            self.requests.load(Ordering::SeqCst)
+        }
+    }
+
+    fn handle_request(mut stream: TcpStream, session: &str, inputs: &HashMap<String, String>) {
+        let mut reader = BufReader::new(stream.try_clone().unwrap());
+
+        let mut request_line = String::new();
+        reader.read_line(&mut request_line).unwrap();
+        let path = request_line
+            .split_whitespace()
+            .nth(1)
+            .unwrap_or_default()
+            .to_string();
+
+        let mut authorized = false;
+        loop {
+            let mut header = String::new();
+            if reader.read_line(&mut header).unwrap() == 0 || header.trim().is_empty() {
+                break;
+            }
+            if let Some((name, value)) = header.split_once(':') {
+                if name.eq_ignore_ascii_case("cookie") && value.trim() == session {
+                    authorized = true;
+                }
+            }
+        }
+
+        let (status, body) = match (authorized, inputs.get(&path)) {
+            (false, _) => ("400 Bad Request", "Puzzle inputs differ by user."),
+            (true, None) => ("404 Not Found", "404 Not Found"),
+            (true, Some(body)) => ("200 OK", body.as_str()),
+        };
+
+        write!(
+            stream,
+            "HTTP/1.1 {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+            status,
+            body.len(),
+            body
+        )
+        .unwrap();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::HashMap, time::Instant};
+
+    use super::{mock_server::MockServer, *};
+    use crate::helpers::test_dir::TestDir;
+
+    fn mock_inputs() -> HashMap<String, String> {
+        HashMap::from([
+            ("/2021/day/1/input".to_string(), "1\n2\n3\n".to_string()),
+            ("/2021/day/2/input".to_string(), "forward 5\n".to_string()),
+        ])
+    }
+
+    #[test]
+    fn fetches_and_caches() {
+        let server = MockServer::start("secret", mock_inputs());
+        let cache_dir = TestDir::new("input_manager_fetches_and_caches");
+        let manager = InputManager::new(
+            2021,
+            cache_dir.path(),
+            HttpBackend::new(server.url(), "secret"),
+        )
+        .with_min_fetch_interval(Duration::ZERO);
+
+        let path = manager.get(1).unwrap();
+        assert_eq!(path, cache_dir.join("day01_complex.txt"));
+        assert_eq!(fs::read_to_string(&path).unwrap(), "1\n2\n3\n");
+        assert_eq!(server.num_requests(), 1);
+
+        let path = manager.get(1).unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "1\n2\n3\n");
+        assert_eq!(server.num_requests(), 1);
+    }
+
+    #[test]
+    fn respects_rate_limit() {
+        let server = MockServer::start("secret", mock_inputs());
+        let cache_dir = TestDir::new("input_manager_respects_rate_limit");
+        let manager = InputManager::new(
+            2021,
+            cache_dir.path(),
+            HttpBackend::new(server.url(), "secret"),
+        )
+        .with_min_fetch_interval(Duration::from_millis(300));
+
+        let t0 = Instant::now();
+        manager.get(1).unwrap();
+        manager.get(2).unwrap();
+        assert!(t0.elapsed() >= Duration::from_millis(300));
+        assert_eq!(server.num_requests(), 2);
+    }
+
+    #[test]
+    fn uses_cache_without_session() {
+        let cache_dir = TestDir::new("input_manager_uses_cache_without_session");
+        fs::write(cache_dir.join("day01_complex.txt"), "1\n2\n3\n").unwrap();
+
+        let backend = HttpBackend {
+            base_url: "http://127.0.0.1:1".to_string(),
+            session: None,
+        };
+        let manager = InputManager::new(2021, cache_dir.path(), backend);
+
+        assert_eq!(manager.get(1).unwrap(), cache_dir.join("day01_complex.txt"));
+        let error = manager.get(2).unwrap_err();
+        assert!(error.to_string().contains(SESSION_ENV_VAR));
+    }
+
+    #[test]
+    fn reports_errors() {
+        let server = MockServer::start("secret", mock_inputs());
+        let cache_dir = TestDir::new("input_manager_reports_errors");
+
+        let manager = InputManager::new(
+            2021,
+            cache_dir.path(),
+            HttpBackend::new(server.url(), "wrong"),
+        )
+        .with_min_fetch_interval(Duration::ZERO);
+        assert!(manager.get(1).is_err());
+        assert!(!manager.cached_path(1).exists());
+
+        let manager = InputManager::new(
+            2021,
+            cache_dir.path(),
+            HttpBackend::new(server.url(), "secret"),
+        )
+        .with_min_fetch_interval(Duration::ZERO);
+        assert!(manager.get(3).is_err());
+        assert!(manager.get(26).is_err());
+    }
+}
@@ -0,0 +1,156 @@
+use std::fmt;
+
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct NormalizationReport {
+    pub removed_bom: bool,
+    pub converted_line_endings: usize,
+    pub trimmed_lines: usize,
+    pub removed_trailing_blank_lines: usize,
+    pub added_final_newline: bool,
+}
+
+impl NormalizationReport {
+    pub fn is_unchanged(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+impl fmt::Display for NormalizationReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut changes = vec![];
+        if self.removed_bom {
+            changes.push("removed byte order mark".to_string());
+        }
+        if self.converted_line_endings > 0 {
+            changes.push(format!(
+                "converted {} line ending(s) to '\\n'",
+                self.converted_line_endings
+            ));
+        }
+        if self.trimmed_lines > 0 {
+            changes.push(format!(
+                "removed trailing whitespace from {} line(s)",
+                self.trimmed_lines
+            ));
+        }
+        if self.removed_trailing_blank_lines > 0 {
+            changes.push(format!(
+                "removed {} trailing blank line(s)",
+                self.removed_trailing_blank_lines
+            ));
+        }
+        if self.added_final_newline {
+            changes.push("added final newline".to_string());
+        }
+
+        if changes.is_empty() {
+            write!(f, "unchanged")
+        } else {
+            write!(f, "{}", changes.join(", "))
+        }
+    }
+}
+
+/// Brings input data into a canonical form, so parsers don't have to care
+/// about how it was saved.
+///
+/// The result has no byte order mark, only `\n` line endings, no trailing
+/// whitespace in any line and ends with exactly one newline.
+/// Leading whitespace is kept, as some inputs depend on it.
+pub fn normalize_input(input_data: &str) -> (String, NormalizationReport) {
+    let mut report = NormalizationReport::default();
+
+    let input_data = match input_data.strip_prefix('\u{feff}') {
+        Some(stripped) => {
+            report.removed_bom = true;
+            stripped
+        }
+        None => input_data,
+    };
+
+    report.converted_line_endings = input_data.matches('\r').count();
+    let input_data = input_data.replace("\r\n", "\n").replace('\r', "\n");
+
+    let mut lines = input_data
+        .split('\n')
+        .map(|line| {
+            let trimmed = line.trim_end();
+            if trimmed.len() != line.len() {
+                report.trimmed_lines += 1;
+            }
+            trimmed
+        })
+        .collect::<Vec<_>>();
+
+    if !matches!(lines.last(), Some(&"")) {
+        report.added_final_newline = true;
+    }
+    while matches!(lines.last(), Some(&"")) {
+        lines.pop();
+        report.removed_trailing_blank_lines += 1;
+    }
+    // The final newline itself shows up as an empty last element
+    report.removed_trailing_blank_lines = report.removed_trailing_blank_lines.saturating_sub(1);
+
+    let mut normalized = lines.join("\n");
+    normalized.push('\n');
+
+    (normalized, report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keeps_canonical_input() {
+        let (normalized, report) = normalize_input("  #A#B#\n1,2\n\nfold\n");
+        assert_eq!(normalized, "  #A#B#\n1,2\n\nfold\n");
+        assert!(report.is_unchanged());
+        assert_eq!(report.to_string(), "unchanged");
+    }
+
+    #[test]
+    fn works() {
+        let (normalized, report) =
+            normalize_input("\u{feff}NNCB \r\n\r\nCH -> B\t\r\nHH -> N\r\n\r\n\r\n");
+        assert_eq!(normalized, "NNCB\n\nCH -> B\nHH -> N\n");
+        assert_eq!(
+            report,
+            NormalizationReport {
+                removed_bom: true,
+                converted_line_endings: 6,
+                trimmed_lines: 2,
+                removed_trailing_blank_lines: 2,
+                added_final_newline: false,
+            }
+        );
+    }
+
+    #[test]
+    fn fixes_windows_inputs() {
+        use crate::solutions::day14;
+
+        let input_file = std::path::PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("input_data")
+            .join("day14_simple.txt");
+        let data = std::fs::read_to_string(input_file).unwrap();
+        let windows_data = format!("\u{feff}{}", data.replace('\n', " \r\n"));
+
+        let (normalized, _) = normalize_input(&windows_data);
+        assert_eq!(
+            day14::task1(&day14::parse_input(&normalized)),
+            day14::task1(&day14::parse_input(&data))
+        );
+    }
+
+    #[test]
+    fn adds_final_newline() {
+        let (normalized, report) = normalize_input("1\r2");
+        assert_eq!(normalized, "1\n2\n");
+        assert_eq!(
+            report.to_string(),
+            "converted 1 line ending(s) to '\\n', added final newline"
+        );
+    }
+}
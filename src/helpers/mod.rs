@@ -1,8 +1,16 @@
 pub mod accu_iter;
+pub mod benchmarking;
+pub mod example_extraction;
 pub mod image_manipulation;
+pub mod input_generation;
+pub mod input_manager;
+pub mod input_normalization;
 pub mod input_parsing;
 pub mod rendering;
+pub mod scaling;
 pub mod temporary_hashset;
+#[cfg(test)]
+pub mod test_dir;
 
 #[macro_export]
 macro_rules! aoc_tests {
@@ -30,6 +38,7 @@ macro_rules! aoc_tests {
                         |e| panic!("Unable to open '{}': {}", input_file.into_os_string().into_string().unwrap(), e)
                     )
                 };
+                let (data, _) = $crate::helpers::input_normalization::normalize_input(&data);
 
                 let input_data = super::parse_input(&data);
                 let actual_result = super::$suite(&input_data);
@@ -46,32 +55,53 @@ macro_rules! aoc_tests {
 macro_rules! solutions {
     ( $( ($day:ident, $($task:ident),* ) )* ) => {
 
-        mod solutions{
+        pub mod solutions {
             $(
                 pub mod $day;
             )*
         }
 
-        fn run_solution(day: u8, task: u8, data: &str) -> Result<String> {
+        pub fn register_solution_benchmarks(
+            registry: &mut impl $crate::helpers::benchmarking::BenchmarkRegistry,
+        ) {
+            $(
+                if let Some(data) = $crate::helpers::benchmarking::load_benchmark_input(stringify!($day)) {
+                    use $crate::helpers::benchmarking::BenchmarkGroup;
+                    let mut group = registry.group(stringify!(solutions), stringify!($day));
+                    group.bench("parse", || {
+                        solutions::$day::parse_input(&data)
+                    });
+                    let input_data = solutions::$day::parse_input(&data);
+                    $(
+                        group.bench(stringify!($task), || {
+                            solutions::$day::$task(&input_data)
+                        });
+                    )*
+                }
+            )*
+        }
+
+        pub fn run_solution(
+            day: u8,
+            task: u8,
+            data: &str,
+        ) -> Result<$crate::helpers::benchmarking::SolutionOutput> {
             let day_str = format!("day{:0>2}", day);
             let task_str = format!("task{}", task);
 
             match (day_str.as_str(), task_str.as_str()) {
                 $($(
                     (stringify!($day), stringify!($task)) => {
-                        println!(
-                            "Running solver {}::{} ...",
-                            stringify!($day),
-                            stringify!($task)
-                        );
                         let t0 = std::time::Instant::now();
                         let input_data = solutions::$day::parse_input(data);
                         let t1 = std::time::Instant::now();
                         let solution = solutions::$day::$task(&input_data);
                         let t2 = std::time::Instant::now();
-                        println!("   ... parse input: {} ms", (t1-t0).as_millis());
-                        println!("   ... calculate: {} ms", (t2-t1).as_millis());
-                        Ok(format!("{}", solution))
+                        Ok($crate::helpers::benchmarking::SolutionOutput {
+                            result: format!("{}", solution),
+                            parse_time: t1 - t0,
+                            calculation_time: t2 - t1,
+                        })
                     },
                 )*)*
                 _ => Err(anyhow!(
@@ -88,13 +118,18 @@ macro_rules! solutions {
 macro_rules! renderers {
     ( $( ($day:ident, $($task:ident),* ) )* ) => {
 
-        mod renderers {
+        pub mod renderers {
             $(
                 pub mod $day;
             )*
         }
 
-        fn run_renderer(day: u8, task: u8, data: &str) -> Result<Vec<String>> {
+        pub fn run_renderer(
+            day: u8,
+            task: u8,
+            data: &str,
+            options: &$crate::helpers::rendering::RenderOptions,
+        ) -> Result<Vec<std::path::PathBuf>> {
             let day_str = format!("day{:0>2}", day);
             let task_str = format!("task{}", task);
 
@@ -107,7 +142,12 @@ macro_rules! renderers {
                             stringify!($task)
                         );
                         let input_data = solutions::$day::parse_input(data);
-                        Ok(renderers::$day::$task(&input_data))
+                        let context = $crate::helpers::rendering::RenderContext::new(
+                            day,
+                            task,
+                            options.clone(),
+                        );
+                        renderers::$day::$task(&input_data, &context)
                     },
                 )*)*
                 _ => Err(anyhow!(
@@ -124,32 +164,53 @@ macro_rules! renderers {
 macro_rules! reworked_solutions {
     ( $( ($day:ident, $($task:ident),* ) )* ) => {
 
-        mod reworked_solutions {
+        pub mod reworked_solutions {
             $(
                 pub mod $day;
             )*
         }
 
-        fn run_reworked_solutions(day: u8, task: u8, data: &str) -> Result<String> {
+        pub fn register_reworked_solution_benchmarks(
+            registry: &mut impl $crate::helpers::benchmarking::BenchmarkRegistry,
+        ) {
+            $(
+                if let Some(data) = $crate::helpers::benchmarking::load_benchmark_input(stringify!($day)) {
+                    use $crate::helpers::benchmarking::BenchmarkGroup;
+                    let mut group = registry.group(stringify!(reworked_solutions), stringify!($day));
+                    group.bench("parse", || {
+                        reworked_solutions::$day::parse_input(&data)
+                    });
+                    let input_data = reworked_solutions::$day::parse_input(&data);
+                    $(
+                        group.bench(stringify!($task), || {
+                            reworked_solutions::$day::$task(&input_data)
+                        });
+                    )*
+                }
+            )*
+        }
+
+        pub fn run_reworked_solutions(
+            day: u8,
+            task: u8,
+            data: &str,
+        ) -> Result<$crate::helpers::benchmarking::SolutionOutput> {
             let day_str = format!("day{:0>2}", day);
             let task_str = format!("task{}", task);
 
             match (day_str.as_str(), task_str.as_str()) {
                 $($(
                     (stringify!($day), stringify!($task)) => {
-                        println!(
-                            "Running reworked solver {}::{} ...",
-                            stringify!($day),
-                            stringify!($task)
-                        );
                         let t0 = std::time::Instant::now();
                         let input_data = reworked_solutions::$day::parse_input(data);
                         let t1 = std::time::Instant::now();
                         let solution = reworked_solutions::$day::$task(&input_data);
                         let t2 = std::time::Instant::now();
-                        println!("   ... parse input: {} ms", (t1-t0).as_millis());
-                        println!("   ... calculate: {} ms", (t2-t1).as_millis());
-                        Ok(format!("{}", solution))
+                        Ok($crate::helpers::benchmarking::SolutionOutput {
+                            result: format!("{}", solution),
+                            parse_time: t1 - t0,
+                            calculation_time: t2 - t1,
+                        })
                     },
                 )*)*
                 _ => Err(anyhow!(
@@ -0,0 +1,131 @@
+use std::{
+    fs::{self, File},
+    io::{BufReader, BufWriter, Read, Write},
+    path::Path,
+};
+
+use anyhow::{ensure, Context, Result};
+
+use super::common::{frame_channel, frame_to_bytes, FrameProgress, FrameReceiver};
+
+pub struct ApngWriter {
+    sleep_time: f64,
+    receiver: FrameReceiver,
+}
+
+impl super::Writer for ApngWriter {
+    fn write(self: Box<Self>, filename: &Path, frame_count_hint: Option<usize>) -> Result<()> {
+        // APNG requires the number of frames in its header, so the raw
+        // frames get spooled to disk until the animation is complete.
+        let spool_path = filename.with_extension("png.part");
+        let result = self.write_spooled(filename, &spool_path, frame_count_hint);
+        if result.is_err() {
+            // Best effort, the original error is more relevant
+            let _ = fs::remove_file(&spool_path);
+        }
+        result
+    }
+}
+
+impl ApngWriter {
+    fn write_spooled(
+        &self,
+        filename: &Path,
+        spool_path: &Path,
+        frame_count_hint: Option<usize>,
+    ) -> Result<()> {
+        let progress = FrameProgress::new(frame_count_hint);
+        let mut spool = BufWriter::new(
+            File::create(spool_path)
+                .with_context(|| format!("Unable to create '{}'", spool_path.display()))?,
+        );
+        let mut timestamps = vec![];
+        let mut dimensions = None;
+        for (frame, timestamp) in self.receiver.iter() {
+            let frame_dimensions = (frame.width() as u32, frame.height() as u32);
+            ensure!(
+                *dimensions.get_or_insert(frame_dimensions) == frame_dimensions,
+                "All frames of an APNG must have the same size!"
+            );
+            spool.write_all(&frame_to_bytes(&frame))?;
+            timestamps.push(timestamp);
+            progress.inc();
+        }
+        progress.finish();
+        spool.flush()?;
+        drop(spool);
+
+        if let Some((width, height)) = dimensions {
+            let file = BufWriter::new(
+                File::create(filename)
+                    .with_context(|| format!("Unable to create '{}'", filename.display()))?,
+            );
+            let mut encoder = png::Encoder::new(file, width, height);
+            encoder.set_color(png::ColorType::Rgba);
+            encoder.set_depth(png::BitDepth::Eight);
+            encoder.set_animated(timestamps.len() as u32, 0)?;
+            let mut writer = encoder.write_header()?;
+
+            let mut spool = BufReader::new(File::open(spool_path)?);
+            let progress = FrameProgress::new(Some(timestamps.len()));
+            let mut bytes = vec![0u8; width as usize * height as usize * 4];
+            for (id, timestamp) in timestamps.iter().enumerate() {
+                let duration = match timestamps.get(id + 1) {
+                    Some(next_timestamp) => next_timestamp - timestamp,
+                    None => self.sleep_time,
+                };
+                let delay_ms = (duration * 1000.0).round().clamp(0.0, u16::MAX as f64) as u16;
+                writer.set_frame_delay(delay_ms, 1000)?;
+
+                spool.read_exact(&mut bytes)?;
+                writer.write_image_data(&bytes)?;
+                progress.inc();
+            }
+            writer.finish()?;
+            progress.finish();
+        }
+        fs::remove_file(spool_path)?;
+
+        Ok(())
+    }
+}
+
+pub fn create_apng(sleep_time: f64) -> Result<(impl super::Collector + Send, impl super::Writer)> {
+    let (collector, receiver) = frame_channel();
+    Ok((
+        collector,
+        ApngWriter {
+            receiver,
+            sleep_time,
+        },
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use imgref::ImgVec;
+    use rgb::RGBA8;
+
+    use super::{super::Collector, super::Writer, *};
+    use crate::helpers::test_dir::TestDir;
+
+    #[test]
+    fn works() {
+        let dir = TestDir::new("apng");
+        let filename = dir.join("animation.png");
+
+        let (mut collector, writer) = create_apng(1.5).unwrap();
+        let frame = |value| ImgVec::new(vec![RGBA8::new(value, value, value, 255); 6], 3, 2);
+        collector.add_frame(frame(0), 0.0).unwrap();
+        collector.add_frame(frame(255), 0.25).unwrap();
+        drop(collector);
+        Box::new(writer).write(&filename, Some(2)).unwrap();
+
+        let decoder = png::Decoder::new(File::open(&filename).unwrap());
+        let reader = decoder.read_info().unwrap();
+        let animation_control = reader.info().animation_control.unwrap();
+        assert_eq!(animation_control.num_frames, 2);
+        assert_eq!((reader.info().width, reader.info().height), (3, 2));
+        assert!(!filename.with_extension("png.part").exists());
+    }
+}
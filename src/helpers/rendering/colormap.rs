@@ -0,0 +1,243 @@
+//! Colormaps for numeric grids.
+//!
+//! Values get normalized into a [`ValueRange`] first, either a fixed one
+//! or one that is derived from the data, so colors never saturate or wrap around.
+
+use ndarray::Array2;
+use num::ToPrimitive;
+use rgb::RGBA8;
+
+use super::ToColor;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Colormap {
+    Viridis,
+    Magma,
+    Grayscale,
+    /// Blue for negative, white for zero, red for positive values
+    Diverging,
+    /// Distinct colors for integer values, without normalization
+    Categorical,
+}
+
+const VIRIDIS: &[u32] = &[
+    0x440154, 0x482475, 0x414487, 0x355f8d, 0x2a788e, 0x21918c, 0x22a884, 0x44bf70, 0x7ad151,
+    0xbddf26, 0xfde725,
+];
+const MAGMA: &[u32] = &[
+    0x000004, 0x140e36, 0x3b0f70, 0x641a80, 0x8c2981, 0xb73779, 0xde4968, 0xf7705c, 0xfe9f6d,
+    0xfecf92, 0xfcfdbf,
+];
+const GRAYSCALE: &[u32] = &[0x000000, 0xffffff];
+const DIVERGING: &[u32] = &[
+    0x2166ac, 0x4393c3, 0x92c5de, 0xd1e5f0, 0xf7f7f7, 0xfddbc7, 0xf4a582, 0xd6604d, 0xb2182b,
+];
+const CATEGORICAL: &[u32] = &[
+    0x1f77b4, 0xff7f0e, 0x2ca02c, 0xd62728, 0x9467bd, 0x8c564b, 0xe377c2, 0x7f7f7f, 0xbcbd22,
+    0x17becf,
+];
+
+fn from_hex(hex: u32) -> RGBA8 {
+    RGBA8::new((hex >> 16) as u8, (hex >> 8) as u8, hex as u8, 255)
+}
+
+/// Linear interpolation between two colors, `amount` is clamped to `0..=1`.
+pub fn mix(from: RGBA8, to: RGBA8, amount: f64) -> RGBA8 {
+    let amount = amount.clamp(0.0, 1.0);
+    let lerp = |a: u8, b: u8| (a as f64 + (b as f64 - a as f64) * amount).round() as u8;
+    RGBA8::new(
+        lerp(from.r, to.r),
+        lerp(from.g, to.g),
+        lerp(from.b, to.b),
+        lerp(from.a, to.a),
+    )
+}
+
+impl Colormap {
+    fn stops(self) -> &'static [u32] {
+        match self {
+            Colormap::Viridis => VIRIDIS,
+            Colormap::Magma => MAGMA,
+            Colormap::Grayscale => GRAYSCALE,
+            Colormap::Diverging => DIVERGING,
+            Colormap::Categorical => CATEGORICAL,
+        }
+    }
+
+    /// The color at position `t`, which gets clamped to `0..=1`.
+    ///
+    /// For `Categorical`, the range is split evenly between all colors.
+    pub fn color(self, t: f64) -> RGBA8 {
+        let stops = self.stops();
+        let t = if t.is_nan() { 0.0 } else { t.clamp(0.0, 1.0) };
+
+        if self == Colormap::Categorical {
+            let index = (t * stops.len() as f64) as usize;
+            return from_hex(stops[index.min(stops.len() - 1)]);
+        }
+
+        let position = t * (stops.len() - 1) as f64;
+        let index = (position.floor() as usize).min(stops.len() - 2);
+        mix(
+            from_hex(stops[index]),
+            from_hex(stops[index + 1]),
+            position - index as f64,
+        )
+    }
+
+    /// A distinct color for every integer, repeating after a while.
+    pub fn categorical(index: i64) -> RGBA8 {
+        from_hex(CATEGORICAL[index.rem_euclid(CATEGORICAL.len() as i64) as usize])
+    }
+
+    /// The color of `value`, normalized into `range`.
+    pub fn color_in_range(self, value: impl ToPrimitive, range: ValueRange) -> RGBA8 {
+        let value = value.to_f64().unwrap_or(f64::NAN);
+        match self {
+            Colormap::Categorical => Self::categorical(value.round() as i64),
+            _ => self.color(range.normalize(value)),
+        }
+    }
+}
+
+/// The values that get mapped onto the full colormap.
+///
+/// `min` may be larger than `max`, to reverse the colormap.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ValueRange {
+    pub min: f64,
+    pub max: f64,
+}
+
+impl ValueRange {
+    pub fn new(min: f64, max: f64) -> Self {
+        Self { min, max }
+    }
+
+    /// The range of all values, ignoring those that aren't representable as numbers.
+    pub fn of<T: ToPrimitive>(values: impl IntoIterator<Item = T>) -> Self {
+        let (min, max) = values
+            .into_iter()
+            .filter_map(|value| value.to_f64())
+            .filter(|value| !value.is_nan())
+            .fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), value| {
+                (min.min(value), max.max(value))
+            });
+        if min > max {
+            Self::new(0.0, 0.0)
+        } else {
+            Self::new(min, max)
+        }
+    }
+
+    /// Extends the range to be centered around zero.
+    pub fn symmetric(self) -> Self {
+        let extent = self.min.abs().max(self.max.abs());
+        Self::new(-extent, extent)
+    }
+
+    pub fn reversed(self) -> Self {
+        Self::new(self.max, self.min)
+    }
+
+    /// Maps the value into `0..=1`. Values outside of the range get clamped.
+    pub fn normalize(&self, value: f64) -> f64 {
+        let extent = self.max - self.min;
+        if extent == 0.0 {
+            return 0.5;
+        }
+        ((value - self.min) / extent).clamp(0.0, 1.0)
+    }
+}
+
+impl ToColor for RGBA8 {
+    fn to_color(&self) -> RGBA8 {
+        *self
+    }
+}
+
+/// A numeric value that gets colored by a colormap, for grids of plain numbers.
+#[derive(Debug, Clone, Copy)]
+pub struct Mapped<T> {
+    pub value: T,
+    pub colormap: Colormap,
+    pub range: ValueRange,
+}
+
+impl<T: ToPrimitive + Copy> ToColor for Mapped<T> {
+    fn to_color(&self) -> RGBA8 {
+        self.colormap.color_in_range(self.value, self.range)
+    }
+}
+
+/// Wraps every cell of a numeric grid, normalizing it into the range of its values.
+///
+/// The result can be passed to [`super::map_to_image`].
+pub fn colorize<T>(map: &Array2<T>, colormap: Colormap) -> Array2<Mapped<T>>
+where
+    T: ToPrimitive + Copy,
+{
+    let range = ValueRange::of(map.iter().copied());
+    let range = match colormap {
+        Colormap::Diverging => range.symmetric(),
+        _ => range,
+    };
+    colorize_in_range(map, colormap, range)
+}
+
+/// Wraps every cell of a numeric grid, normalizing it into a fixed range.
+pub fn colorize_in_range<T>(
+    map: &Array2<T>,
+    colormap: Colormap,
+    range: ValueRange,
+) -> Array2<Mapped<T>>
+where
+    T: ToPrimitive + Copy,
+{
+    map.mapv(|value| Mapped {
+        value,
+        colormap,
+        range,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn works() {
+        assert_eq!(
+            Colormap::Viridis.color(0.0),
+            RGBA8::new(0x44, 0x01, 0x54, 255)
+        );
+        assert_eq!(
+            Colormap::Viridis.color(2.0),
+            RGBA8::new(0xfd, 0xe7, 0x25, 255)
+        );
+        assert_eq!(
+            Colormap::Grayscale.color(0.5),
+            RGBA8::new(128, 128, 128, 255)
+        );
+
+        let range = ValueRange::of([3u8, 7, 5]);
+        assert_eq!(range, ValueRange::new(3.0, 7.0));
+        assert_eq!(range.normalize(6.0), 0.75);
+        assert_eq!(range.reversed().normalize(6.0), 0.25);
+        assert_eq!(range.normalize(100.0), 1.0);
+        assert_eq!(
+            ValueRange::of([-2i32, 1]).symmetric(),
+            ValueRange::new(-2.0, 2.0)
+        );
+
+        let map = Array2::from_shape_vec((1, 3), vec![-4i64, 0, 2]).unwrap();
+        let colors = colorize(&map, Colormap::Diverging);
+        assert_eq!(colors[(0, 0)].to_color(), Colormap::Diverging.color(0.0));
+        assert_eq!(colors[(0, 1)].to_color(), Colormap::Diverging.color(0.5));
+
+        let colors = colorize(&map, Colormap::Categorical);
+        assert_eq!(colors[(0, 1)].to_color(), Colormap::categorical(0));
+        assert_eq!(colors[(0, 2)].to_color(), Colormap::categorical(2));
+        assert_eq!(colors[(0, 0)].to_color(), Colormap::categorical(6));
+    }
+}
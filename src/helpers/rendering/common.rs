@@ -1,8 +1,16 @@
+use anyhow::{anyhow, Result};
 use imgref::ImgVec;
-use ndarray::{Array2, Axis};
+use indicatif::{ProgressBar, ProgressStyle};
+use ndarray::{ArrayBase, Axis, Data, Ix2};
 use rgb::RGBA8;
 
-pub fn map_to_image(map: &Array2<impl super::ToColor>, scale: usize) -> ImgVec<RGBA8> {
+use super::ToColor;
+
+pub fn map_to_image<S>(map: &ArrayBase<S, Ix2>, scale: usize) -> ImgVec<RGBA8>
+where
+    S: Data,
+    S::Elem: ToColor,
+{
     let mut pixels = vec![];
 
     for row in map.axis_iter(Axis(0)) {
@@ -20,3 +28,146 @@ pub fn map_to_image(map: &Array2<impl super::ToColor>, scale: usize) -> ImgVec<R
 
     ImgVec::<RGBA8>::new(pixels, map.dim().1 * scale, map.dim().0 * scale)
 }
+
+pub type FrameSender = std::sync::mpsc::SyncSender<(ImgVec<RGBA8>, f64)>;
+pub type FrameReceiver = std::sync::mpsc::Receiver<(ImgVec<RGBA8>, f64)>;
+
+/// A collector that forwards all frames to a writer running on another thread.
+pub struct ChannelCollector {
+    frames_count: usize,
+    sender: FrameSender,
+}
+
+pub fn frame_channel() -> (ChannelCollector, FrameReceiver) {
+    let (sender, receiver) = std::sync::mpsc::sync_channel(4);
+    (
+        ChannelCollector {
+            sender,
+            frames_count: 0,
+        },
+        receiver,
+    )
+}
+
+impl super::Collector for ChannelCollector {
+    fn add_frame(&mut self, frame: ImgVec<RGBA8>, timestamp: f64) -> Result<()> {
+        self.sender
+            .send((frame, timestamp))
+            .map_err(|_| anyhow!("The writer stopped receiving frames"))?;
+        self.frames_count += 1;
+        Ok(())
+    }
+    fn get_num_frames(&self) -> usize {
+        self.frames_count
+    }
+}
+
+pub fn frame_to_bytes(frame: &ImgVec<RGBA8>) -> Vec<u8> {
+    frame
+        .pixels()
+        .flat_map(|pixel| [pixel.r, pixel.g, pixel.b, pixel.a].into_iter())
+        .collect()
+}
+
+/// Progress of a writer, in frames.
+///
+/// The frame count hint is only an estimate, so the bar grows if more frames arrive.
+/// Without a hint, a spinner with the number of written frames is shown instead.
+pub struct FrameProgress {
+    bar: ProgressBar,
+}
+
+impl FrameProgress {
+    pub fn new(frame_count_hint: Option<usize>) -> Self {
+        let bar = match frame_count_hint {
+            Some(frame_count) => ProgressBar::new(frame_count as u64),
+            None => {
+                let bar = ProgressBar::new_spinner();
+                bar.set_style(
+                    ProgressStyle::default_spinner().template("{spinner} {pos} frames [{elapsed}]"),
+                );
+                bar
+            }
+        };
+        Self { bar }
+    }
+
+    pub fn inc(&self) {
+        if self.bar.length() <= self.bar.position() {
+            self.bar.set_length(self.bar.position() + 1);
+        }
+        self.bar.inc(1);
+    }
+
+    pub fn finish(&self) {
+        self.bar.set_length(self.bar.position());
+        self.bar.finish();
+    }
+}
+
+impl gifski::progress::ProgressReporter for FrameProgress {
+    fn increase(&mut self) -> bool {
+        self.inc();
+        true
+    }
+    fn done(&mut self, _msg: &str) {
+        self.finish();
+    }
+}
+
+/// Collects frames from within callbacks that can't return errors, like those of
+/// a search that can't be aborted from within.
+///
+/// Remembers the first failure and skips all later frames, so the failure can be
+/// reported once the callback-driven algorithm is done.
+#[derive(Default)]
+pub struct ErrorLatch {
+    error: Option<anyhow::Error>,
+}
+
+impl ErrorLatch {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Runs the action, unless an earlier one failed.
+    pub fn run(&mut self, action: impl FnOnce() -> Result<()>) {
+        if self.error.is_none() {
+            self.error = action().err();
+        }
+    }
+
+    pub fn into_result(self) -> Result<()> {
+        match self.error {
+            Some(error) => Err(error),
+            None => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn works() {
+        let mut runs = 0;
+        let mut latch = ErrorLatch::new();
+        latch.run(|| {
+            runs += 1;
+            Ok(())
+        });
+        latch.run(|| {
+            runs += 1;
+            Err(anyhow!("first"))
+        });
+        latch.run(|| {
+            runs += 1;
+            Err(anyhow!("second"))
+        });
+        assert_eq!(runs, 2);
+        assert_eq!(latch.into_result().unwrap_err().to_string(), "first");
+
+        assert!(ErrorLatch::new().into_result().is_ok());
+    }
+}
@@ -0,0 +1,122 @@
+//! A minimal Graphviz DOT document builder, for puzzles with graph data.
+//!
+//! All ids and attribute values get quoted, so any text can be used.
+
+use std::{fs, path::Path};
+
+use anyhow::{Context, Result};
+use rgb::RGBA8;
+
+pub struct DotGraph {
+    name: String,
+    directed: bool,
+    statements: Vec<String>,
+}
+
+/// A color as DOT attribute value.
+pub fn color(color: RGBA8) -> String {
+    format!("#{:02x}{:02x}{:02x}", color.r, color.g, color.b)
+}
+
+fn quote(text: &str) -> String {
+    format!(
+        "\"{}\"",
+        text.replace('\\', "\\\\")
+            .replace('"', "\\\"")
+            .replace('\n', "\\n")
+    )
+}
+
+fn attribute_list(attributes: &[(&str, &str)]) -> String {
+    if attributes.is_empty() {
+        return String::new();
+    }
+    let attributes = attributes
+        .iter()
+        .map(|(key, value)| format!("{}={}", key, quote(value)))
+        .collect::<Vec<_>>()
+        .join(", ");
+    format!(" [{}]", attributes)
+}
+
+impl DotGraph {
+    pub fn new(name: &str, directed: bool) -> Self {
+        Self {
+            name: name.to_string(),
+            directed,
+            statements: vec![],
+        }
+    }
+
+    /// Default attributes for the `graph`, all `node`s or all `edge`s.
+    pub fn defaults(&mut self, kind: &str, attributes: &[(&str, &str)]) {
+        self.statements
+            .push(format!("{}{};", kind, attribute_list(attributes)));
+    }
+
+    pub fn node(&mut self, id: &str, attributes: &[(&str, &str)]) {
+        self.statements
+            .push(format!("{}{};", quote(id), attribute_list(attributes)));
+    }
+
+    pub fn edge(&mut self, from: &str, to: &str, attributes: &[(&str, &str)]) {
+        self.statements.push(format!(
+            "{} {} {}{};",
+            quote(from),
+            if self.directed { "->" } else { "--" },
+            quote(to),
+            attribute_list(attributes)
+        ));
+    }
+
+    pub fn to_document(&self) -> String {
+        let mut document = format!(
+            "{} {} {{\n",
+            if self.directed { "digraph" } else { "graph" },
+            quote(&self.name)
+        );
+        for statement in &self.statements {
+            document.push_str("  ");
+            document.push_str(statement);
+            document.push('\n');
+        }
+        document.push_str("}\n");
+        document
+    }
+
+    pub fn save(&self, filename: &Path) -> Result<()> {
+        fs::write(filename, self.to_document())
+            .with_context(|| format!("Unable to write '{}'", filename.display()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn works() {
+        let mut graph = DotGraph::new("caves", false);
+        graph.defaults("node", &[("shape", "box")]);
+        graph.node("start", &[("color", &color(RGBA8::new(255, 0, 128, 255)))]);
+        graph.edge("start", "a\"b", &[("label", "1\n2")]);
+
+        assert_eq!(
+            graph.to_document(),
+            concat!(
+                "graph \"caves\" {\n",
+                "  node [shape=\"box\"];\n",
+                "  \"start\" [color=\"#ff0080\"];\n",
+                "  \"start\" -- \"a\\\"b\" [label=\"1\\n2\"];\n",
+                "}\n"
+            )
+        );
+
+        let mut graph = DotGraph::new("packets", true);
+        graph.edge("a", "b", &[]);
+        assert_eq!(
+            graph.to_document(),
+            "digraph \"packets\" {\n  \"a\" -> \"b\";\n}\n"
+        );
+    }
+}
@@ -0,0 +1,125 @@
+use anyhow::Result;
+use imgref::ImgVec;
+use rgb::RGBA8;
+
+use super::Collector;
+
+/// Drops frames that barely differ from the previously kept one.
+///
+/// Writers show every frame until the timestamp of the next one, so the
+/// kept frames simply stay visible longer. A threshold of zero only drops
+/// frames that are identical to the previous one.
+pub struct FrameFilter<C> {
+    collector: C,
+    min_changed_fraction: f64,
+    frames_count: usize,
+    previous: Option<ImgVec<RGBA8>>,
+    /// The newest frame, if it got dropped
+    pending: Option<(ImgVec<RGBA8>, f64)>,
+}
+
+impl<C: Collector> FrameFilter<C> {
+    pub fn new(collector: C, min_changed_fraction: f64) -> Self {
+        Self {
+            collector,
+            min_changed_fraction,
+            frames_count: 0,
+            previous: None,
+            pending: None,
+        }
+    }
+
+    fn is_significant_change(&self, frame: &ImgVec<RGBA8>) -> bool {
+        let previous = match &self.previous {
+            Some(previous) => previous,
+            None => return true,
+        };
+        if (previous.width(), previous.height()) != (frame.width(), frame.height()) {
+            return true;
+        }
+
+        let num_changed = previous
+            .pixels()
+            .zip(frame.pixels())
+            .filter(|(a, b)| a != b)
+            .count();
+        let num_pixels = (frame.width() * frame.height()).max(1);
+
+        num_changed > 0 && num_changed as f64 / num_pixels as f64 >= self.min_changed_fraction
+    }
+}
+
+impl<C: Collector> Collector for FrameFilter<C> {
+    fn add_frame(&mut self, frame: ImgVec<RGBA8>, timestamp: f64) -> Result<()> {
+        // Renderers derive timestamps from this, so it counts all frames, including dropped ones
+        self.frames_count += 1;
+
+        if self.is_significant_change(&frame) {
+            self.pending = None;
+            self.previous = Some(frame.clone());
+            self.collector.add_frame(frame, timestamp)
+        } else {
+            self.pending = Some((frame, timestamp));
+            Ok(())
+        }
+    }
+    fn get_num_frames(&self) -> usize {
+        self.frames_count
+    }
+    fn finish(&mut self) -> Result<()> {
+        // Keep the timing of the end of the animation, and its final state
+        if let Some((frame, timestamp)) = self.pending.take() {
+            self.collector.add_frame(frame, timestamp)?;
+        }
+        self.collector.finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Default)]
+    struct Recorder {
+        frames: Vec<(u8, f64)>,
+    }
+
+    impl Collector for Recorder {
+        fn add_frame(&mut self, frame: ImgVec<RGBA8>, timestamp: f64) -> Result<()> {
+            self.frames.push((frame.buf()[0].r, timestamp));
+            Ok(())
+        }
+        fn get_num_frames(&self) -> usize {
+            self.frames.len()
+        }
+    }
+
+    fn frame(first: u8, second: u8) -> ImgVec<RGBA8> {
+        ImgVec::new(
+            vec![RGBA8::new(first, 0, 0, 255), RGBA8::new(second, 0, 0, 255)],
+            2,
+            1,
+        )
+    }
+
+    fn filter(min_changed_fraction: f64, frames: &[(u8, u8)]) -> Vec<(u8, f64)> {
+        let mut recorder = Recorder::default();
+        let mut filter = FrameFilter::new(&mut recorder, min_changed_fraction);
+        for (id, &(first, second)) in frames.iter().enumerate() {
+            filter.add_frame(frame(first, second), id as f64).unwrap();
+        }
+        assert_eq!(filter.get_num_frames(), frames.len());
+        filter.finish().unwrap();
+        recorder.frames
+    }
+
+    #[test]
+    fn works() {
+        let frames = [(1, 0), (1, 0), (2, 0), (3, 1), (3, 1), (3, 1)];
+        assert_eq!(
+            filter(0.0, &frames),
+            vec![(1, 0.0), (2, 2.0), (3, 3.0), (3, 5.0)]
+        );
+        assert_eq!(filter(1.0, &frames), vec![(1, 0.0), (3, 3.0), (3, 5.0)]);
+    }
+}
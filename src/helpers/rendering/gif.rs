@@ -1,10 +1,13 @@
-use std::fs::File;
+use std::{fs::File, path::Path};
 
-use ndarray::Array2;
+use anyhow::{Context, Result};
+use imgref::ImgVec;
+use rgb::RGBA8;
+
+use super::common::FrameProgress;
 
 pub struct GifskyCollector {
     collector: gifski::Collector,
-    scale: usize,
     sleep_time: f64,
     frames_count: usize,
 }
@@ -14,46 +17,42 @@ pub struct GifskyWriter {
 }
 
 impl super::Writer for GifskyWriter {
-    fn write(self, filename: &impl AsRef<std::path::Path>, frame_count_hint: Option<usize>) {
-        let file = File::create(filename).unwrap();
-        if let Some(frame_count_hint) = frame_count_hint {
-            let mut progress = gifski::progress::ProgressBar::new(frame_count_hint as u64);
-            self.writer.write(&file, &mut progress).unwrap();
-        } else {
-            let mut progress = gifski::progress::NoProgress {};
-            self.writer.write(&file, &mut progress).unwrap();
-        }
+    fn write(self: Box<Self>, filename: &Path, frame_count_hint: Option<usize>) -> Result<()> {
+        let file = File::create(filename)
+            .with_context(|| format!("Unable to create '{}'", filename.display()))?;
+        let mut progress = FrameProgress::new(frame_count_hint);
+        self.writer.write(&file, &mut progress)?;
+        Ok(())
     }
 }
 
-pub fn create_gifski(scale: usize, sleep_time: f64) -> (impl super::Collector, impl super::Writer) {
+pub fn create_gifski(
+    sleep_time: f64,
+) -> Result<(impl super::Collector + Send, impl super::Writer)> {
     let (collector, writer) = gifski::new(gifski::Settings {
         quality: 100,
         fast: false,
         repeat: gifski::Repeat::Infinite,
         width: None,
         height: None,
-    })
-    .unwrap();
+    })?;
 
-    (
+    Ok((
         GifskyCollector {
             collector,
-            scale,
             sleep_time,
             frames_count: 0,
         },
         GifskyWriter { writer },
-    )
+    ))
 }
 
 impl super::Collector for GifskyCollector {
-    fn add_frame(&mut self, data: &Array2<impl super::ToColor>, timestamp: f64) {
-        let img = super::common::map_to_image(data, self.scale);
+    fn add_frame(&mut self, frame: ImgVec<RGBA8>, timestamp: f64) -> Result<()> {
         self.collector
-            .add_frame_rgba(self.frames_count, img, timestamp + self.sleep_time)
-            .unwrap();
+            .add_frame_rgba(self.frames_count, frame, timestamp + self.sleep_time)?;
         self.frames_count += 1;
+        Ok(())
     }
     fn get_num_frames(&self) -> usize {
         self.frames_count
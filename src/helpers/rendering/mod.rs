@@ -1,41 +1,166 @@
-use std::path::Path;
+use std::{
+    any::Any,
+    fmt,
+    path::{Path, PathBuf},
+    str::FromStr,
+    thread::JoinHandle,
+};
 
-use ndarray::Array2;
+use anyhow::{anyhow, Result};
+use imgref::ImgVec;
 use rgb::RGBA8;
 
+mod apng;
+pub mod colormap;
 mod common;
+pub mod dot;
+mod frame_filter;
 mod gif;
+mod output;
+pub mod overlay;
+mod png_frames;
+pub mod resize;
+pub mod svg;
+mod terminal;
 mod webp;
+mod y4m;
+
+pub use common::{map_to_image, ErrorLatch};
+pub use frame_filter::FrameFilter;
+pub use output::{RenderContext, RenderOptions};
+pub use png_frames::write_png;
 
 pub trait ToColor {
     fn to_color(&self) -> RGBA8;
 }
 
 pub trait Writer {
-    fn write(self, filename: &impl AsRef<Path>, frame_count_hint: Option<usize>);
+    fn write(self: Box<Self>, filename: &Path, frame_count_hint: Option<usize>) -> Result<()>;
 }
 
 pub trait Collector {
-    fn add_frame(&mut self, data: &Array2<impl ToColor>, timestamp: f64);
+    fn add_frame(&mut self, frame: ImgVec<RGBA8>, timestamp: f64) -> Result<()>;
     fn get_num_frames(&self) -> usize;
+    /// Called after the last frame, for collectors that hold frames back.
+    fn finish(&mut self) -> Result<()> {
+        Ok(())
+    }
 }
 
-pub struct FramesCounter {
-    count: usize,
+impl<C: Collector + ?Sized> Collector for Box<C> {
+    fn add_frame(&mut self, frame: ImgVec<RGBA8>, timestamp: f64) -> Result<()> {
+        (**self).add_frame(frame, timestamp)
+    }
+    fn get_num_frames(&self) -> usize {
+        (**self).get_num_frames()
+    }
+    fn finish(&mut self) -> Result<()> {
+        (**self).finish()
+    }
 }
 
-impl Collector for FramesCounter {
-    fn add_frame(&mut self, _data: &Array2<impl ToColor>, _timestamp: f64) {
-        self.count += 1;
+impl<C: Collector + ?Sized> Collector for &mut C {
+    fn add_frame(&mut self, frame: ImgVec<RGBA8>, timestamp: f64) -> Result<()> {
+        (**self).add_frame(frame, timestamp)
     }
     fn get_num_frames(&self) -> usize {
-        self.count
+        (**self).get_num_frames()
+    }
+    fn finish(&mut self) -> Result<()> {
+        (**self).finish()
     }
 }
 
-impl FramesCounter {
-    pub fn new() -> Self {
-        Self { count: 0 }
+pub type BoxedCollector = Box<dyn Collector + Send>;
+pub type BoxedWriter = Box<dyn Writer>;
+
+/// Waits for the thread that feeds a collector, turning a panic into an error.
+pub fn join_collector_thread<T>(thread: JoinHandle<Result<T>>) -> Result<T> {
+    thread.join().map_err(|panic: Box<dyn Any + Send>| {
+        let message = panic
+            .downcast_ref::<&str>()
+            .map(|message| message.to_string())
+            .or_else(|| panic.downcast_ref::<String>().cloned())
+            .unwrap_or_else(|| "unknown reason".to_string());
+        anyhow!("Collector thread panicked: {}", message)
+    })?
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RenderFormat {
+    Gif,
+    Webp,
+    Apng,
+    PngFrames,
+    Y4m,
+    Terminal,
+}
+
+impl RenderFormat {
+    pub const ALL: &'static [&'static str] =
+        &["gif", "webp", "apng", "png-frames", "y4m", "terminal"];
+
+    pub fn extension(self) -> &'static str {
+        match self {
+            RenderFormat::Gif => "gif",
+            RenderFormat::Webp => "webp",
+            RenderFormat::Apng => "png",
+            // A directory of frames
+            RenderFormat::PngFrames => "",
+            RenderFormat::Y4m => "y4m",
+            RenderFormat::Terminal => "",
+        }
+    }
+
+    /// The format that writes files with the given extension.
+    pub fn from_extension(extension: &str) -> Option<Self> {
+        [
+            RenderFormat::Gif,
+            RenderFormat::Webp,
+            RenderFormat::Apng,
+            RenderFormat::Y4m,
+        ]
+        .into_iter()
+        .find(|format| format.extension() == extension)
+    }
+
+    /// Whether the format produces an artifact on disk.
+    pub fn writes_file(self) -> bool {
+        self != RenderFormat::Terminal
+    }
+}
+
+impl FromStr for RenderFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "gif" => Ok(RenderFormat::Gif),
+            "webp" => Ok(RenderFormat::Webp),
+            "apng" => Ok(RenderFormat::Apng),
+            "png-frames" => Ok(RenderFormat::PngFrames),
+            "y4m" => Ok(RenderFormat::Y4m),
+            "terminal" => Ok(RenderFormat::Terminal),
+            _ => Err(format!(
+                "Unknown render format '{}', expected one of: {}",
+                s,
+                Self::ALL.join(", ")
+            )),
+        }
+    }
+}
+
+impl fmt::Display for RenderFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            RenderFormat::Gif => "gif",
+            RenderFormat::Webp => "webp",
+            RenderFormat::Apng => "apng",
+            RenderFormat::PngFrames => "png-frames",
+            RenderFormat::Y4m => "y4m",
+            RenderFormat::Terminal => "terminal",
+        };
+        write!(f, "{}", name)
     }
 }
 
@@ -43,12 +168,78 @@ pub struct Renderers {
     _inconstructible: (),
 }
 
-#[allow(dead_code)]
 impl Renderers {
-    pub fn create_gif_renderer(scale: usize, sleep_time: f64) -> (impl Collector, impl Writer) {
-        self::gif::create_gifski(scale, sleep_time)
+    pub fn create(format: RenderFormat, sleep_time: f64) -> Result<(BoxedCollector, BoxedWriter)> {
+        match format {
+            RenderFormat::Gif => Self::create_gif_renderer(sleep_time),
+            RenderFormat::Webp => Self::create_webp_renderer(sleep_time),
+            RenderFormat::Apng => Self::create_apng_renderer(sleep_time),
+            RenderFormat::PngFrames => Self::create_png_frames_renderer(sleep_time),
+            RenderFormat::Y4m => Self::create_y4m_renderer(sleep_time),
+            RenderFormat::Terminal => Self::create_terminal_renderer(sleep_time),
+        }
+    }
+    pub fn create_gif_renderer(sleep_time: f64) -> Result<(BoxedCollector, BoxedWriter)> {
+        let (collector, writer) = self::gif::create_gifski(sleep_time)?;
+        Ok((Box::new(collector), Box::new(writer)))
+    }
+    pub fn create_webp_renderer(sleep_time: f64) -> Result<(BoxedCollector, BoxedWriter)> {
+        let (collector, writer) = self::webp::create_webp(sleep_time)?;
+        Ok((Box::new(collector), Box::new(writer)))
+    }
+    pub fn create_apng_renderer(sleep_time: f64) -> Result<(BoxedCollector, BoxedWriter)> {
+        let (collector, writer) = self::apng::create_apng(sleep_time)?;
+        Ok((Box::new(collector), Box::new(writer)))
     }
-    pub fn create_webp_renderer(scale: usize, sleep_time: f64) -> (impl Collector, impl Writer) {
-        self::webp::create_webp(scale, sleep_time)
+    pub fn create_png_frames_renderer(sleep_time: f64) -> Result<(BoxedCollector, BoxedWriter)> {
+        let (collector, writer) = self::png_frames::create_png_frames(sleep_time)?;
+        Ok((Box::new(collector), Box::new(writer)))
     }
+    pub fn create_y4m_renderer(sleep_time: f64) -> Result<(BoxedCollector, BoxedWriter)> {
+        let (collector, writer) = self::y4m::create_y4m(sleep_time)?;
+        Ok((Box::new(collector), Box::new(writer)))
+    }
+    pub fn create_terminal_renderer(sleep_time: f64) -> Result<(BoxedCollector, BoxedWriter)> {
+        let (collector, writer) = self::terminal::create_terminal(sleep_time)?;
+        Ok((Box::new(collector), Box::new(writer)))
+    }
+}
+
+/// Runs a simulation on its own thread, while the writer encodes the frames it produces.
+///
+/// The frame count estimate is only used for displaying progress.
+pub fn render_animation<F>(
+    context: &RenderContext,
+    preferred_format: RenderFormat,
+    sleep_time: f64,
+    frame_count_estimate: Option<usize>,
+    simulate: F,
+) -> Result<Vec<PathBuf>>
+where
+    F: FnOnce(&mut BoxedCollector) -> Result<()> + Send + 'static,
+{
+    let format = context.animation_format(preferred_format)?;
+    let (mut collector, writer) = Renderers::create(format, sleep_time)?;
+    if let Some(min_changed_fraction) = context.options().min_changed_fraction() {
+        collector = Box::new(FrameFilter::new(collector, min_changed_fraction));
+    }
+
+    let collector_thread = std::thread::spawn(move || {
+        simulate(&mut collector)?;
+        collector.finish()
+    });
+
+    // `animation_format` already checked the extension of the output file
+    let filename = context.artifact_path(None, format.extension());
+    let write_result = writer.write(&filename, frame_count_estimate);
+    let collect_result = join_collector_thread(collector_thread);
+    // A failing writer makes the collector fail as well, so report the writer's error first
+    write_result?;
+    collect_result?;
+
+    Ok(if format.writes_file() {
+        vec![filename]
+    } else {
+        vec![]
+    })
 }
@@ -0,0 +1,255 @@
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{anyhow, bail, Result};
+
+use super::{
+    resize::{FrameLayout, FrameSize, Viewport},
+    RenderFormat,
+};
+
+/// The rendering settings chosen by the user.
+#[derive(Debug, Clone, Default)]
+pub struct RenderOptions {
+    /// A directory to write all artifacts to, or the file name of the main artifact.
+    /// Defaults to the current directory.
+    pub output: Option<PathBuf>,
+    /// The format of animations. Defaults to the preferred format of the renderer.
+    pub format: Option<RenderFormat>,
+    /// Merge identical consecutive frames into one that is shown longer.
+    pub dedup_frames: bool,
+    /// Only keep frames in which at least this fraction of pixels changed.
+    pub min_frame_change: Option<f64>,
+    /// Annotate frames with captions and legends, if the renderer supports them.
+    pub captions: bool,
+    /// Fit frames into this size, instead of using the renderer's scale.
+    pub frame_size: Option<FrameSize>,
+    /// Only render this section of the grid.
+    pub viewport: Option<Viewport>,
+}
+
+impl RenderOptions {
+    pub fn frame_layout(&self) -> FrameLayout {
+        FrameLayout {
+            viewport: self.viewport,
+            size: self.frame_size,
+        }
+    }
+
+    /// The fraction of pixels that has to change for a frame to be kept, if frames get filtered.
+    pub fn min_changed_fraction(&self) -> Option<f64> {
+        match (self.dedup_frames, self.min_frame_change) {
+            (_, Some(min_frame_change)) => Some(min_frame_change),
+            (true, None) => Some(0.0),
+            (false, None) => None,
+        }
+    }
+
+    pub fn create_output_directory(&self) -> io::Result<()> {
+        match &self.output {
+            Some(output) if is_directory(output) => fs::create_dir_all(output),
+            Some(output) => match output.parent() {
+                Some(parent) if !parent.as_os_str().is_empty() => fs::create_dir_all(parent),
+                _ => Ok(()),
+            },
+            None => Ok(()),
+        }
+    }
+}
+
+/// Everything a renderer needs to know about where and how to render.
+pub struct RenderContext {
+    day: u8,
+    task: u8,
+    options: RenderOptions,
+}
+
+impl RenderContext {
+    pub fn new(day: u8, task: u8, options: RenderOptions) -> Self {
+        Self { day, task, options }
+    }
+
+    pub fn options(&self) -> &RenderOptions {
+        &self.options
+    }
+
+    /// The lowercase extension of the output file name, if the user specified one.
+    fn output_extension(&self) -> Option<String> {
+        self.options
+            .output
+            .as_deref()
+            .filter(|output| !is_directory(output))
+            .and_then(|output| output.extension())
+            .map(|extension| extension.to_string_lossy().to_lowercase())
+    }
+
+    /// The format of animations: the one chosen by the user, the one matching
+    /// the extension of the output file, or the renderer's preferred one.
+    pub fn animation_format(&self, preferred: RenderFormat) -> Result<RenderFormat> {
+        let extension = self.output_extension();
+
+        let format = match (self.options.format, &extension) {
+            (Some(format), _) => format,
+            (None, Some(extension)) => {
+                RenderFormat::from_extension(extension).ok_or_else(|| {
+                    anyhow!("Unable to infer an animation format from '.{}'", extension)
+                })?
+            }
+            (None, None) => preferred,
+        };
+
+        if let Some(extension) = extension {
+            if format.writes_file() && format.extension() != extension {
+                bail!(
+                    "The output file extension '.{}' doesn't match the animation format '{}'",
+                    extension,
+                    format
+                );
+            }
+        }
+        Ok(format)
+    }
+
+    fn default_file_stem(&self, variant: Option<&str>) -> String {
+        let mut stem = format!("aoc2021_day{:0>2}_task{}", self.day, self.task);
+        if let Some(variant) = variant {
+            stem.push('_');
+            stem.push_str(variant);
+        }
+        stem
+    }
+
+    /// The path of the main rendering artifact.
+    ///
+    /// Artifacts are named `aoc2021_dayXX_taskN[_variant].<extension>`,
+    /// unless the user specified a file name, in which case its extension
+    /// has to match the artifact's.
+    pub fn output_path(&self, variant: Option<&str>, extension: &str) -> Result<PathBuf> {
+        if let Some(output_extension) = self.output_extension() {
+            if output_extension != extension {
+                bail!(
+                    "The output file extension '.{}' doesn't match the rendered '.{}' file",
+                    output_extension,
+                    extension
+                );
+            }
+        }
+        Ok(self.artifact_path(variant, extension))
+    }
+
+    /// The path of any rendering artifact, without checking its extension.
+    /// Additional artifacts are named like the main one, but may have a different extension.
+    ///
+    /// If the user specified a file name, the variant gets appended to it.
+    pub fn artifact_path(&self, variant: Option<&str>, extension: &str) -> PathBuf {
+        let output = match &self.options.output {
+            Some(output) => output.clone(),
+            None => std::env::current_dir().unwrap(),
+        };
+
+        if is_directory(&output) {
+            return output
+                .join(self.default_file_stem(variant))
+                .with_extension(extension);
+        }
+
+        let mut file_name = output.file_stem().unwrap_or_default().to_os_string();
+        if let Some(variant) = variant {
+            file_name.push("_");
+            file_name.push(variant);
+        }
+        output.with_file_name(file_name).with_extension(extension)
+    }
+}
+
+fn is_directory(path: &Path) -> bool {
+    path.is_dir()
+        || path.extension().is_none()
+        || path
+            .as_os_str()
+            .to_string_lossy()
+            .ends_with(std::path::MAIN_SEPARATOR)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn context(output: Option<&str>, format: Option<RenderFormat>) -> RenderContext {
+        RenderContext::new(
+            15,
+            2,
+            RenderOptions {
+                output: output.map(PathBuf::from),
+                format,
+                ..Default::default()
+            },
+        )
+    }
+
+    fn output_path(output: Option<&str>, variant: Option<&str>, extension: &str) -> PathBuf {
+        context(output, None)
+            .output_path(variant, extension)
+            .unwrap()
+    }
+
+    fn artifact_path(output: Option<&str>, variant: Option<&str>, extension: &str) -> PathBuf {
+        context(output, None).artifact_path(variant, extension)
+    }
+
+    #[test]
+    fn works() {
+        assert_eq!(
+            output_path(None, None, "webp"),
+            std::env::current_dir()
+                .unwrap()
+                .join("aoc2021_day15_task2.webp")
+        );
+        assert_eq!(
+            output_path(Some("visualizations"), Some("astar"), "gif"),
+            PathBuf::from("visualizations/aoc2021_day15_task2_astar.gif")
+        );
+        assert_eq!(
+            output_path(Some("out/path.webp"), None, "webp"),
+            PathBuf::from("out/path.webp")
+        );
+        assert_eq!(
+            artifact_path(Some("out/path.webp"), Some("astar"), "gif"),
+            PathBuf::from("out/path_astar.gif")
+        );
+        assert_eq!(
+            artifact_path(Some("out/path.png"), None, "svg"),
+            PathBuf::from("out/path.svg")
+        );
+        assert!(context(Some("out/path.webp"), None)
+            .output_path(None, "png")
+            .is_err());
+        assert!(context(Some("out/path.PNG"), None)
+            .output_path(None, "png")
+            .is_ok());
+
+        let animation_format =
+            |output, format| context(output, format).animation_format(RenderFormat::Webp);
+        assert_eq!(animation_format(None, None).unwrap(), RenderFormat::Webp);
+        assert_eq!(
+            animation_format(Some("out"), None).unwrap(),
+            RenderFormat::Webp
+        );
+        assert_eq!(
+            animation_format(Some("out/x.gif"), None).unwrap(),
+            RenderFormat::Gif
+        );
+        assert_eq!(
+            animation_format(Some("out/x.png"), None).unwrap(),
+            RenderFormat::Apng
+        );
+        assert_eq!(
+            animation_format(Some("out/x.gif"), Some(RenderFormat::Gif)).unwrap(),
+            RenderFormat::Gif
+        );
+        assert!(animation_format(Some("out/x.gif"), Some(RenderFormat::Webp)).is_err());
+        assert!(animation_format(Some("out/x.mp4"), None).is_err());
+    }
+}
@@ -0,0 +1,229 @@
+//! Text, legends and simple shapes, drawn on top of rendered frames.
+//!
+//! Text uses an embedded 3x5 pixel font that covers digits, letters
+//! (lowercase is drawn as uppercase) and common punctuation.
+
+use imgref::ImgVec;
+use rgb::RGBA8;
+
+const GLYPH_WIDTH: usize = 3;
+const GLYPH_HEIGHT: usize = 5;
+const GLYPH_SPACING: usize = 1;
+const LINE_SPACING: usize = 2;
+
+const TEXT_COLOR: RGBA8 = RGBA8::new(255, 255, 255, 255);
+const BACKGROUND_COLOR: RGBA8 = RGBA8::new(0, 0, 0, 160);
+
+/// The rows of a glyph, the three lowest bits of every row are its pixels.
+fn glyph(c: char) -> [u8; GLYPH_HEIGHT] {
+    match c.to_ascii_uppercase() {
+        '0' => [0b111, 0b101, 0b101, 0b101, 0b111],
+        '1' => [0b010, 0b110, 0b010, 0b010, 0b111],
+        '2' => [0b111, 0b001, 0b111, 0b100, 0b111],
+        '3' => [0b111, 0b001, 0b111, 0b001, 0b111],
+        '4' => [0b101, 0b101, 0b111, 0b001, 0b001],
+        '5' => [0b111, 0b100, 0b111, 0b001, 0b111],
+        '6' => [0b111, 0b100, 0b111, 0b101, 0b111],
+        '7' => [0b111, 0b001, 0b010, 0b010, 0b010],
+        '8' => [0b111, 0b101, 0b111, 0b101, 0b111],
+        '9' => [0b111, 0b101, 0b111, 0b001, 0b111],
+        'A' => [0b010, 0b101, 0b111, 0b101, 0b101],
+        'B' => [0b110, 0b101, 0b110, 0b101, 0b110],
+        'C' => [0b011, 0b100, 0b100, 0b100, 0b011],
+        'D' => [0b110, 0b101, 0b101, 0b101, 0b110],
+        'E' => [0b111, 0b100, 0b110, 0b100, 0b111],
+        'F' => [0b111, 0b100, 0b110, 0b100, 0b100],
+        'G' => [0b011, 0b100, 0b101, 0b101, 0b011],
+        'H' => [0b101, 0b101, 0b111, 0b101, 0b101],
+        'I' => [0b111, 0b010, 0b010, 0b010, 0b111],
+        'J' => [0b001, 0b001, 0b001, 0b101, 0b010],
+        'K' => [0b101, 0b101, 0b110, 0b101, 0b101],
+        'L' => [0b100, 0b100, 0b100, 0b100, 0b111],
+        'M' => [0b101, 0b111, 0b111, 0b101, 0b101],
+        'N' => [0b110, 0b101, 0b101, 0b101, 0b101],
+        'O' => [0b010, 0b101, 0b101, 0b101, 0b010],
+        'P' => [0b110, 0b101, 0b110, 0b100, 0b100],
+        'Q' => [0b010, 0b101, 0b101, 0b110, 0b011],
+        'R' => [0b110, 0b101, 0b110, 0b101, 0b101],
+        'S' => [0b011, 0b100, 0b010, 0b001, 0b110],
+        'T' => [0b111, 0b010, 0b010, 0b010, 0b010],
+        'U' => [0b101, 0b101, 0b101, 0b101, 0b111],
+        'V' => [0b101, 0b101, 0b101, 0b101, 0b010],
+        'W' => [0b101, 0b101, 0b111, 0b111, 0b101],
+        'X' => [0b101, 0b101, 0b010, 0b101, 0b101],
+        'Y' => [0b101, 0b101, 0b010, 0b010, 0b010],
+        'Z' => [0b111, 0b001, 0b010, 0b100, 0b111],
+        ' ' => [0b000, 0b000, 0b000, 0b000, 0b000],
+        ':' => [0b000, 0b010, 0b000, 0b010, 0b000],
+        '.' => [0b000, 0b000, 0b000, 0b000, 0b010],
+        ',' => [0b000, 0b000, 0b000, 0b010, 0b100],
+        '-' => [0b000, 0b000, 0b111, 0b000, 0b000],
+        '+' => [0b000, 0b010, 0b111, 0b010, 0b000],
+        '=' => [0b000, 0b111, 0b000, 0b111, 0b000],
+        '/' => [0b001, 0b001, 0b010, 0b100, 0b100],
+        '%' => [0b101, 0b001, 0b010, 0b100, 0b101],
+        '#' => [0b101, 0b111, 0b101, 0b111, 0b101],
+        '(' => [0b010, 0b100, 0b100, 0b100, 0b010],
+        ')' => [0b010, 0b001, 0b001, 0b001, 0b010],
+        '<' => [0b001, 0b010, 0b100, 0b010, 0b001],
+        '>' => [0b100, 0b010, 0b001, 0b010, 0b100],
+        '!' => [0b010, 0b010, 0b010, 0b000, 0b010],
+        _ => [0b111, 0b001, 0b010, 0b000, 0b010],
+    }
+}
+
+/// Blends `color` over a pixel, respecting the alpha channel of `color`.
+fn blend(pixel: &mut RGBA8, color: RGBA8) {
+    let alpha = color.a as u32;
+    let mix =
+        |below: u8, above: u8| ((below as u32 * (255 - alpha) + above as u32 * alpha) / 255) as u8;
+    *pixel = RGBA8::new(
+        mix(pixel.r, color.r),
+        mix(pixel.g, color.g),
+        mix(pixel.b, color.b),
+        pixel.a.max(color.a),
+    );
+}
+
+/// Fills a rectangle, clipped to the frame.
+pub fn fill_rect(
+    frame: &mut ImgVec<RGBA8>,
+    position: (usize, usize),
+    size: (usize, usize),
+    color: RGBA8,
+) {
+    let x_end = (position.0 + size.0).min(frame.width());
+    let y_end = (position.1 + size.1).min(frame.height());
+    for row in frame.rows_mut().take(y_end).skip(position.1) {
+        for pixel in row.iter_mut().take(x_end).skip(position.0) {
+            blend(pixel, color);
+        }
+    }
+}
+
+/// The size of a text in pixels, if drawn with the given scale.
+pub fn text_size(text: &str, scale: usize) -> (usize, usize) {
+    let lines = text.lines().collect::<Vec<_>>();
+    let max_chars = lines
+        .iter()
+        .map(|line| line.chars().count())
+        .max()
+        .unwrap_or(0);
+
+    let width = (max_chars * (GLYPH_WIDTH + GLYPH_SPACING)).saturating_sub(GLYPH_SPACING);
+    let height = (lines.len() * (GLYPH_HEIGHT + LINE_SPACING)).saturating_sub(LINE_SPACING);
+    (width * scale, height * scale)
+}
+
+/// Draws a text, with its top left corner at `position`.
+pub fn draw_text(
+    frame: &mut ImgVec<RGBA8>,
+    text: &str,
+    position: (usize, usize),
+    scale: usize,
+    color: RGBA8,
+) {
+    for (line_id, line) in text.lines().enumerate() {
+        let y = position.1 + line_id * (GLYPH_HEIGHT + LINE_SPACING) * scale;
+        for (char_id, c) in line.chars().enumerate() {
+            let x = position.0 + char_id * (GLYPH_WIDTH + GLYPH_SPACING) * scale;
+            for (glyph_y, glyph_row) in glyph(c).iter().enumerate() {
+                for glyph_x in 0..GLYPH_WIDTH {
+                    if glyph_row & (1 << (GLYPH_WIDTH - 1 - glyph_x)) != 0 {
+                        fill_rect(
+                            frame,
+                            (x + glyph_x * scale, y + glyph_y * scale),
+                            (scale, scale),
+                            color,
+                        );
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// A text scale that keeps captions readable, but small compared to the frame.
+pub fn default_text_scale(frame: &ImgVec<RGBA8>) -> usize {
+    (frame.width().min(frame.height()) / 150).max(1)
+}
+
+/// Draws a caption onto a darkened box in the top left corner of the frame.
+pub fn draw_caption(frame: &mut ImgVec<RGBA8>, text: &str) {
+    let scale = default_text_scale(frame);
+    let margin = 2 * scale;
+    let (width, height) = text_size(text, scale);
+
+    fill_rect(
+        frame,
+        (0, 0),
+        (width + 2 * margin, height + 2 * margin),
+        BACKGROUND_COLOR,
+    );
+    draw_text(frame, text, (margin, margin), scale, TEXT_COLOR);
+}
+
+/// Draws a legend onto a darkened box in the bottom left corner of the frame,
+/// with a colored square in front of every label.
+pub fn draw_legend(frame: &mut ImgVec<RGBA8>, entries: &[(RGBA8, &str)]) {
+    let scale = default_text_scale(frame);
+    let margin = 2 * scale;
+    let text = entries
+        .iter()
+        .map(|(_, label)| *label)
+        .collect::<Vec<_>>()
+        .join("\n");
+    let (text_width, height) = text_size(&text, scale);
+    let swatch_size = GLYPH_HEIGHT * scale;
+    let width = swatch_size + margin + text_width;
+
+    let top = frame.height().saturating_sub(height + 2 * margin);
+    fill_rect(
+        frame,
+        (0, top),
+        (width + 2 * margin, height + 2 * margin),
+        BACKGROUND_COLOR,
+    );
+    for (id, (color, _)) in entries.iter().enumerate() {
+        let y = top + margin + id * (GLYPH_HEIGHT + LINE_SPACING) * scale;
+        fill_rect(frame, (margin, y), (swatch_size, swatch_size), *color);
+    }
+    draw_text(
+        frame,
+        &text,
+        (2 * margin + swatch_size, top + margin),
+        scale,
+        TEXT_COLOR,
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn works() {
+        assert_eq!(text_size("AB\nC", 1), (7, 12));
+        assert_eq!(text_size("AB\nC", 2), (14, 24));
+
+        let black = RGBA8::new(0, 0, 0, 255);
+        let mut frame = ImgVec::new(vec![black; 8 * 6], 8, 6);
+        draw_text(&mut frame, "1", (1, 0), 1, TEXT_COLOR);
+
+        let rendered = frame
+            .rows()
+            .map(|row| {
+                row.iter()
+                    .map(|pixel| if *pixel == black { '.' } else { '#' })
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            rendered,
+            vec!["..#.....", ".##.....", "..#.....", "..#.....", ".###....", "........"]
+        );
+
+        // Clipped at the border
+        draw_text(&mut frame, "8", (6, 4), 1, TEXT_COLOR);
+    }
+}
@@ -0,0 +1,110 @@
+use std::{
+    fs::{self, File},
+    io::{BufWriter, Write},
+    path::Path,
+};
+
+use anyhow::{Context, Result};
+use imgref::ImgVec;
+use rgb::RGBA8;
+
+use super::common::{frame_channel, frame_to_bytes, FrameProgress, FrameReceiver};
+
+pub struct PngFramesWriter {
+    sleep_time: f64,
+    receiver: FrameReceiver,
+}
+
+pub fn write_png(filename: &Path, frame: &ImgVec<RGBA8>) -> Result<()> {
+    let file = BufWriter::new(
+        File::create(filename)
+            .with_context(|| format!("Unable to create '{}'", filename.display()))?,
+    );
+    let mut encoder = png::Encoder::new(file, frame.width() as u32, frame.height() as u32);
+    encoder.set_color(png::ColorType::Rgba);
+    encoder.set_depth(png::BitDepth::Eight);
+    let mut writer = encoder.write_header()?;
+    writer.write_image_data(&frame_to_bytes(frame))?;
+    Ok(())
+}
+
+impl super::Writer for PngFramesWriter {
+    /// Writes the frames as `frame_XXXXX.png` into the directory `filename`,
+    /// together with a `timing.csv` that lists when and how long every frame is shown.
+    fn write(self: Box<Self>, filename: &Path, frame_count_hint: Option<usize>) -> Result<()> {
+        fs::create_dir_all(filename)
+            .with_context(|| format!("Unable to create '{}'", filename.display()))?;
+        let progress = FrameProgress::new(frame_count_hint);
+
+        let mut timestamps = vec![];
+        for (frame_id, (frame, timestamp)) in self.receiver.iter().enumerate() {
+            let frame_name = format!("frame_{:0>5}.png", frame_id);
+            write_png(&filename.join(&frame_name), &frame)?;
+            timestamps.push((frame_name, timestamp));
+            progress.inc();
+        }
+
+        let manifest_path = filename.join("timing.csv");
+        let mut manifest = BufWriter::new(
+            File::create(&manifest_path)
+                .with_context(|| format!("Unable to create '{}'", manifest_path.display()))?,
+        );
+        writeln!(manifest, "file,timestamp_ms,duration_ms")?;
+        for (id, (frame_name, timestamp)) in timestamps.iter().enumerate() {
+            let duration = match timestamps.get(id + 1) {
+                Some((_, next_timestamp)) => next_timestamp - timestamp,
+                None => self.sleep_time,
+            };
+            writeln!(
+                manifest,
+                "{},{},{}",
+                frame_name,
+                (timestamp * 1000.0).round(),
+                (duration * 1000.0).round()
+            )?;
+        }
+        manifest.flush()?;
+        progress.finish();
+        Ok(())
+    }
+}
+
+pub fn create_png_frames(
+    sleep_time: f64,
+) -> Result<(impl super::Collector + Send, impl super::Writer)> {
+    let (collector, receiver) = frame_channel();
+    Ok((
+        collector,
+        PngFramesWriter {
+            receiver,
+            sleep_time,
+        },
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{super::Collector, super::Writer, *};
+    use crate::helpers::test_dir::TestDir;
+
+    #[test]
+    fn works() {
+        let dir = TestDir::new("png_frames");
+
+        let (mut collector, writer) = create_png_frames(1.5).unwrap();
+        let frame = |value| ImgVec::new(vec![RGBA8::new(value, value, value, 255); 6], 3, 2);
+        collector.add_frame(frame(0), 0.0).unwrap();
+        collector.add_frame(frame(255), 0.25).unwrap();
+        drop(collector);
+        Box::new(writer).write(dir.path(), Some(2)).unwrap();
+
+        assert_eq!(
+            fs::read_to_string(dir.join("timing.csv")).unwrap(),
+            "file,timestamp_ms,duration_ms\n\
+             frame_00000.png,0,250\n\
+             frame_00001.png,250,1500\n"
+        );
+        assert!(dir.join("frame_00000.png").exists());
+        assert!(dir.join("frame_00001.png").exists());
+    }
+}
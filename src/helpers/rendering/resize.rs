@@ -0,0 +1,267 @@
+//! Fitting grids of any size into frames of a requested size.
+
+use std::str::FromStr;
+
+use anyhow::{ensure, Result};
+use imgref::ImgVec;
+use ndarray::{s, Array2};
+use rgb::RGBA8;
+
+use super::{map_to_image, ToColor};
+
+/// A rectangular section of a grid, in grid cells.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Viewport {
+    pub x: usize,
+    pub y: usize,
+    pub width: usize,
+    pub height: usize,
+}
+
+impl FromStr for Viewport {
+    type Err = String;
+
+    /// Parses `X,Y,WIDTHxHEIGHT`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let error = || format!("Invalid viewport '{}', expected X,Y,WIDTHxHEIGHT", s);
+        let (x, rest) = s.split_once(',').ok_or_else(error)?;
+        let (y, size) = rest.split_once(',').ok_or_else(error)?;
+        let FrameSize { width, height } = size.parse().map_err(|_| error())?;
+        Ok(Self {
+            x: x.trim().parse().map_err(|_| error())?,
+            y: y.trim().parse().map_err(|_| error())?,
+            width,
+            height,
+        })
+    }
+}
+
+/// The size of a frame, in pixels.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FrameSize {
+    pub width: usize,
+    pub height: usize,
+}
+
+impl FromStr for FrameSize {
+    type Err = String;
+
+    /// Parses `WIDTHxHEIGHT`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let error = || format!("Invalid size '{}', expected WIDTHxHEIGHT", s);
+        let (width, height) = s.split_once('x').ok_or_else(error)?;
+        let width = width.trim().parse().map_err(|_| error())?;
+        let height = height.trim().parse().map_err(|_| error())?;
+        if width == 0 || height == 0 {
+            return Err(error());
+        }
+        Ok(Self { width, height })
+    }
+}
+
+/// How grids get turned into frames.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct FrameLayout {
+    /// Only render this section of the grid
+    pub viewport: Option<Viewport>,
+    /// Scale the frame to fit into this size, keeping its aspect ratio
+    pub size: Option<FrameSize>,
+}
+
+impl FrameLayout {
+    /// Crops the grid to the viewport and converts it to an image.
+    ///
+    /// Without a target size, every cell becomes `scale`x`scale` pixels.
+    /// Viewports may reach past the edge of the grid, but have to start inside of it.
+    pub fn map_to_image(&self, map: &Array2<impl ToColor>, scale: usize) -> Result<ImgVec<RGBA8>> {
+        let (rows, columns) = map.dim();
+        ensure!(rows > 0 && columns > 0, "Unable to render an empty grid");
+
+        let view = match self.viewport {
+            Some(viewport) => {
+                ensure!(
+                    viewport.width > 0 && viewport.height > 0,
+                    "The viewport {}x{} is empty",
+                    viewport.width,
+                    viewport.height
+                );
+                ensure!(
+                    viewport.x < columns && viewport.y < rows,
+                    "The viewport offset {},{} is outside of the {}x{} grid",
+                    viewport.x,
+                    viewport.y,
+                    columns,
+                    rows
+                );
+                let width = viewport.width.min(columns - viewport.x);
+                let height = viewport.height.min(rows - viewport.y);
+                map.slice(s![
+                    viewport.y..viewport.y + height,
+                    viewport.x..viewport.x + width
+                ])
+            }
+            None => map.view(),
+        };
+
+        let size = match self.size {
+            Some(size) => size,
+            None => return Ok(map_to_image(&view, scale)),
+        };
+
+        let (height, width) = view.dim();
+        let factor = f64::min(
+            size.width as f64 / width as f64,
+            size.height as f64 / height as f64,
+        );
+        if factor >= 1.0 && factor.fract() == 0.0 {
+            // Keep cells crisp
+            return Ok(map_to_image(&view, factor as usize));
+        }
+
+        let image = map_to_image(&view, 1);
+        Ok(resize(
+            &image,
+            ((width as f64 * factor).round() as usize).max(1),
+            ((height as f64 * factor).round() as usize).max(1),
+        ))
+    }
+}
+
+/// The source pixels that overlap with an output pixel, and by how much.
+fn coverage(index: usize, factor: f64, source_length: usize) -> Vec<(usize, f64)> {
+    let start = index as f64 * factor;
+    let end = (index + 1) as f64 * factor;
+    let last = (end.ceil() as usize).min(source_length);
+
+    (start.floor() as usize..last)
+        .map(|source| {
+            let overlap = end.min(source as f64 + 1.0) - start.max(source as f64);
+            (source, overlap)
+        })
+        .filter(|(_, overlap)| *overlap > 0.0)
+        .collect()
+}
+
+/// Resizes the image to an arbitrary size.
+///
+/// Every output pixel is the average of the area it covers in the source image,
+/// weighted by how much of each source pixel is covered.
+pub fn resize(image: &ImgVec<RGBA8>, width: usize, height: usize) -> ImgVec<RGBA8> {
+    let factor_x = image.width() as f64 / width as f64;
+    let factor_y = image.height() as f64 / height as f64;
+    let columns = (0..width)
+        .map(|x| coverage(x, factor_x, image.width()))
+        .collect::<Vec<_>>();
+
+    let mut pixels = Vec::with_capacity(width * height);
+    for y in 0..height {
+        let rows = coverage(y, factor_y, image.height());
+        for column in &columns {
+            let mut sum = [0.0; 4];
+            let mut total_weight = 0.0;
+            for &(source_y, weight_y) in &rows {
+                let row = &image.buf()[source_y * image.stride()..];
+                for &(source_x, weight_x) in column {
+                    let pixel = row[source_x];
+                    let weight = weight_x * weight_y;
+                    sum[0] += pixel.r as f64 * weight;
+                    sum[1] += pixel.g as f64 * weight;
+                    sum[2] += pixel.b as f64 * weight;
+                    sum[3] += pixel.a as f64 * weight;
+                    total_weight += weight;
+                }
+            }
+            let channel = |value: f64| (value / total_weight).round() as u8;
+            pixels.push(RGBA8::new(
+                channel(sum[0]),
+                channel(sum[1]),
+                channel(sum[2]),
+                channel(sum[3]),
+            ));
+        }
+    }
+
+    ImgVec::new(pixels, width, height)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn gray(value: u8) -> RGBA8 {
+        RGBA8::new(value, value, value, 255)
+    }
+
+    #[test]
+    fn works() {
+        let image = ImgVec::new(
+            vec![
+                gray(0),
+                gray(100),
+                gray(200),
+                gray(40),
+                gray(20),
+                gray(60),
+                gray(0),
+                gray(0),
+            ],
+            4,
+            2,
+        );
+        assert_eq!(
+            resize(&image, 2, 1).pixels().collect::<Vec<_>>(),
+            vec![gray(45), gray(60)]
+        );
+
+        let image = ImgVec::new(vec![gray(0), gray(100)], 2, 1);
+        assert_eq!(
+            resize(&image, 3, 1).pixels().collect::<Vec<_>>(),
+            vec![gray(0), gray(50), gray(100)]
+        );
+
+        assert_eq!(
+            "3,4,10x20".parse(),
+            Ok(Viewport {
+                x: 3,
+                y: 4,
+                width: 10,
+                height: 20
+            })
+        );
+        assert!("3,4".parse::<Viewport>().is_err());
+        assert!("0x20".parse::<FrameSize>().is_err());
+
+        let map = Array2::from_shape_fn((10, 20), |(y, x)| gray((y * 20 + x) as u8));
+        let layout = FrameLayout {
+            viewport: Some(Viewport {
+                x: 15,
+                y: 2,
+                width: 10,
+                height: 3,
+            }),
+            size: Some(FrameSize {
+                width: 10,
+                height: 100,
+            }),
+        };
+        let frame = layout.map_to_image(&map, 1).unwrap();
+        assert_eq!((frame.width(), frame.height()), (10, 6));
+        assert_eq!(frame.buf()[0], gray(55));
+
+        let viewport = |x, y, width, height| FrameLayout {
+            viewport: Some(Viewport {
+                x,
+                y,
+                width,
+                height,
+            }),
+            size: None,
+        };
+        assert!(viewport(19, 9, 5, 5).map_to_image(&map, 1).is_ok());
+        assert!(viewport(20, 0, 5, 5).map_to_image(&map, 1).is_err());
+        assert!(viewport(0, 10, 5, 5).map_to_image(&map, 1).is_err());
+        assert!(viewport(0, 0, 0, 5).map_to_image(&map, 1).is_err());
+        let empty = Array2::from_elem((0, 20), gray(0));
+        assert!(FrameLayout::default().map_to_image(&empty, 1).is_err());
+    }
+}
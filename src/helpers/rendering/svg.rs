@@ -0,0 +1,229 @@
+//! A minimal SVG document builder, for puzzles with geometric data.
+//!
+//! Coordinates are in user units; the document is sized to its view box.
+
+use std::{fmt::Write as _, fs, path::Path};
+
+use anyhow::{Context, Result};
+use rgb::RGBA8;
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Style {
+    pub stroke: Option<RGBA8>,
+    pub stroke_width: f64,
+    pub fill: Option<RGBA8>,
+    /// Length of dashes and gaps, for dashed strokes
+    pub dash: Option<f64>,
+}
+
+impl Style {
+    pub fn stroke(color: RGBA8, width: f64) -> Self {
+        Self {
+            stroke: Some(color),
+            stroke_width: width,
+            ..Default::default()
+        }
+    }
+
+    pub fn fill(color: RGBA8) -> Self {
+        Self {
+            fill: Some(color),
+            ..Default::default()
+        }
+    }
+
+    pub fn dashed(self, dash: f64) -> Self {
+        Self {
+            dash: Some(dash),
+            ..self
+        }
+    }
+
+    fn attributes(&self) -> String {
+        let mut attributes = String::new();
+        match self.fill {
+            Some(fill) => write!(attributes, r#" fill="{}""#, hex(fill)).unwrap(),
+            None => attributes.push_str(r#" fill="none""#),
+        }
+        if let Some(fill) = self.fill.filter(|fill| fill.a < 255) {
+            write!(attributes, r#" fill-opacity="{}""#, opacity(fill)).unwrap();
+        }
+        if let Some(stroke) = self.stroke {
+            write!(
+                attributes,
+                r#" stroke="{}" stroke-width="{}" stroke-linecap="round""#,
+                hex(stroke),
+                self.stroke_width
+            )
+            .unwrap();
+            if stroke.a < 255 {
+                write!(attributes, r#" stroke-opacity="{}""#, opacity(stroke)).unwrap();
+            }
+        }
+        if let Some(dash) = self.dash {
+            write!(attributes, r#" stroke-dasharray="{}""#, dash).unwrap();
+        }
+        attributes
+    }
+}
+
+fn hex(color: RGBA8) -> String {
+    format!("#{:02x}{:02x}{:02x}", color.r, color.g, color.b)
+}
+
+fn opacity(color: RGBA8) -> String {
+    format!("{:.3}", color.a as f64 / 255.0)
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+pub struct Svg {
+    width: f64,
+    height: f64,
+    scale: f64,
+    elements: Vec<String>,
+}
+
+impl Svg {
+    pub fn new(width: f64, height: f64) -> Self {
+        Self {
+            width,
+            height,
+            scale: 1.0,
+            elements: vec![],
+        }
+    }
+
+    /// Displays the drawing `scale` times larger than its coordinate space.
+    pub fn scaled(mut self, scale: f64) -> Self {
+        self.scale = scale;
+        self
+    }
+
+    pub fn background(&mut self, color: RGBA8) {
+        self.rect((0.0, 0.0), (self.width, self.height), Style::fill(color));
+    }
+
+    pub fn line(&mut self, from: (f64, f64), to: (f64, f64), style: Style) {
+        self.elements.push(format!(
+            r#"<line x1="{}" y1="{}" x2="{}" y2="{}"{}/>"#,
+            from.0,
+            from.1,
+            to.0,
+            to.1,
+            style.attributes()
+        ));
+    }
+
+    pub fn point(&mut self, center: (f64, f64), radius: f64, color: RGBA8) {
+        self.elements.push(format!(
+            r#"<circle cx="{}" cy="{}" r="{}"{}/>"#,
+            center.0,
+            center.1,
+            radius,
+            Style::fill(color).attributes()
+        ));
+    }
+
+    pub fn rect(&mut self, position: (f64, f64), size: (f64, f64), style: Style) {
+        self.elements.push(format!(
+            r#"<rect x="{}" y="{}" width="{}" height="{}"{}/>"#,
+            position.0,
+            position.1,
+            size.0,
+            size.1,
+            style.attributes()
+        ));
+    }
+
+    pub fn polygon(&mut self, points: &[(f64, f64)], style: Style) {
+        let points = points
+            .iter()
+            .map(|(x, y)| format!("{},{}", x, y))
+            .collect::<Vec<_>>()
+            .join(" ");
+        self.elements.push(format!(
+            r#"<polygon points="{}"{}/>"#,
+            points,
+            style.attributes()
+        ));
+    }
+
+    /// Text with its baseline starting at `position`.
+    pub fn text(&mut self, position: (f64, f64), text: &str, size: f64, color: RGBA8) {
+        self.elements.push(format!(
+            r#"<text x="{}" y="{}" font-family="monospace" font-size="{}"{}>{}</text>"#,
+            position.0,
+            position.1,
+            size,
+            Style::fill(color).attributes(),
+            escape(text)
+        ));
+    }
+
+    pub fn to_document(&self) -> String {
+        let mut document = format!(
+            concat!(
+                r#"<svg xmlns="http://www.w3.org/2000/svg" "#,
+                r#"width="{0}" height="{1}" viewBox="0 0 {2} {3}">"#,
+                "\n"
+            ),
+            self.width * self.scale,
+            self.height * self.scale,
+            self.width,
+            self.height
+        );
+        for element in &self.elements {
+            document.push_str("  ");
+            document.push_str(element);
+            document.push('\n');
+        }
+        document.push_str("</svg>\n");
+        document
+    }
+
+    pub fn save(&self, filename: &Path) -> Result<()> {
+        fs::write(filename, self.to_document())
+            .with_context(|| format!("Unable to write '{}'", filename.display()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn works() {
+        let mut svg = Svg::new(10.0, 5.0);
+        svg.line(
+            (0.0, 0.0),
+            (10.0, 5.0),
+            Style::stroke(RGBA8::new(255, 0, 0, 128), 0.5).dashed(1.0),
+        );
+        svg.point((1.0, 2.0), 0.5, RGBA8::new(0, 0, 255, 255));
+        svg.polygon(&[(0.0, 0.0), (1.0, 0.0), (0.0, 1.0)], Style::default());
+        svg.text((0.0, 5.0), "a<b", 2.0, RGBA8::new(0, 0, 0, 255));
+
+        assert_eq!(
+            svg.to_document(),
+            concat!(
+                r#"<svg xmlns="http://www.w3.org/2000/svg" width="10" height="5" viewBox="0 0 10 5">"#,
+                "\n",
+                r##"  <line x1="0" y1="0" x2="10" y2="5" fill="none" stroke="#ff0000" stroke-width="0.5" stroke-linecap="round" stroke-opacity="0.502" stroke-dasharray="1"/>"##,
+                "\n",
+                r##"  <circle cx="1" cy="2" r="0.5" fill="#0000ff"/>"##,
+                "\n",
+                r#"  <polygon points="0,0 1,0 0,1" fill="none"/>"#,
+                "\n",
+                r##"  <text x="0" y="5" font-family="monospace" font-size="2" fill="#000000">a&lt;b</text>"##,
+                "\n",
+                "</svg>\n"
+            )
+        );
+    }
+}
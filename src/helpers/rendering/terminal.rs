@@ -0,0 +1,161 @@
+use std::{
+    fmt::Write as _,
+    io::{self, Write},
+    path::Path,
+    time::{Duration, Instant},
+};
+
+use anyhow::Result;
+use imgref::ImgVec;
+use rgb::RGBA8;
+use terminal_size::{terminal_size, Height, Width};
+
+use super::resize::resize;
+
+/// Draws frames directly into the terminal, as soon as they arrive.
+///
+/// Every character cell shows two pixels, using the upper half block
+/// with separate foreground and background colors.
+pub struct TerminalCollector {
+    sleep_time: f64,
+    frames_count: usize,
+    /// When the first frame was shown, and its timestamp
+    start: Option<(Instant, f64)>,
+}
+
+/// Nothing to write, everything already happened in the terminal.
+pub struct TerminalWriter {}
+
+impl super::Writer for TerminalWriter {
+    fn write(self: Box<Self>, _filename: &Path, _frame_count_hint: Option<usize>) -> Result<()> {
+        Ok(())
+    }
+}
+
+/// The number of pixels that fit into the terminal, leaving one line for the cursor.
+fn available_pixels() -> (usize, usize) {
+    let (Width(columns), Height(rows)) = terminal_size().unwrap_or((Width(80), Height(24)));
+    (
+        (columns as usize).max(1),
+        2 * (rows as usize).saturating_sub(1).max(1),
+    )
+}
+
+/// Shrinks the frame until it fits, keeping its aspect ratio.
+fn fit(frame: &ImgVec<RGBA8>, max_width: usize, max_height: usize) -> ImgVec<RGBA8> {
+    let factor = f64::min(
+        max_width as f64 / frame.width() as f64,
+        max_height as f64 / frame.height() as f64,
+    );
+    if factor >= 1.0 {
+        return frame.clone();
+    }
+
+    resize(
+        frame,
+        ((frame.width() as f64 * factor).round() as usize).max(1),
+        ((frame.height() as f64 * factor).round() as usize).max(1),
+    )
+}
+
+fn draw(frame: &ImgVec<RGBA8>) -> String {
+    let mut output = String::from("\x1b[H");
+    let rows = frame.rows().collect::<Vec<_>>();
+    for pair in rows.chunks(2) {
+        for (x, top) in pair[0].iter().enumerate() {
+            write!(output, "\x1b[38;2;{};{};{}m", top.r, top.g, top.b).unwrap();
+            match pair.get(1) {
+                Some(bottom) => {
+                    let bottom = bottom[x];
+                    write!(output, "\x1b[48;2;{};{};{}m", bottom.r, bottom.g, bottom.b).unwrap()
+                }
+                None => output.push_str("\x1b[49m"),
+            }
+            output.push('▀');
+        }
+        output.push_str("\x1b[0m\n");
+    }
+    output
+}
+
+impl super::Collector for TerminalCollector {
+    fn add_frame(&mut self, frame: ImgVec<RGBA8>, timestamp: f64) -> Result<()> {
+        let (max_width, max_height) = available_pixels();
+        let frame = fit(&frame, max_width, max_height);
+
+        let (start_instant, start_timestamp) = *self.start.get_or_insert_with(|| {
+            print!("\x1b[2J\x1b[?25l");
+            (Instant::now(), timestamp)
+        });
+        let show_at =
+            start_instant + Duration::from_secs_f64((timestamp - start_timestamp).max(0.0));
+        if let Some(remaining) = show_at.checked_duration_since(Instant::now()) {
+            std::thread::sleep(remaining);
+        }
+
+        let mut stdout = io::stdout().lock();
+        stdout.write_all(draw(&frame).as_bytes())?;
+        stdout.flush()?;
+
+        self.frames_count += 1;
+        Ok(())
+    }
+    fn get_num_frames(&self) -> usize {
+        self.frames_count
+    }
+}
+
+impl Drop for TerminalCollector {
+    fn drop(&mut self) {
+        if self.start.is_some() {
+            std::thread::sleep(Duration::from_secs_f64(self.sleep_time));
+            print!("\x1b[0m\x1b[?25h");
+            // Nothing sensible left to do if the terminal is gone
+            let _ = io::stdout().flush();
+        }
+    }
+}
+
+pub fn create_terminal(
+    sleep_time: f64,
+) -> Result<(impl super::Collector + Send, impl super::Writer)> {
+    Ok((
+        TerminalCollector {
+            sleep_time,
+            frames_count: 0,
+            start: None,
+        },
+        TerminalWriter {},
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn works() {
+        let frame = ImgVec::new(
+            vec![
+                RGBA8::new(0, 0, 0, 255),
+                RGBA8::new(100, 0, 0, 255),
+                RGBA8::new(0, 200, 0, 255),
+                RGBA8::new(0, 0, 0, 255),
+                RGBA8::new(20, 20, 20, 255),
+                RGBA8::new(0, 0, 0, 255),
+            ],
+            3,
+            2,
+        );
+
+        let small = fit(&frame, 2, 2);
+        assert_eq!((small.width(), small.height()), (2, 1));
+        assert_eq!(
+            small.pixels().collect::<Vec<_>>(),
+            vec![RGBA8::new(20, 3, 3, 255), RGBA8::new(20, 70, 3, 255)]
+        );
+
+        assert_eq!(fit(&frame, 3, 2).pixels().count(), 6);
+        assert_eq!(fit(&frame, 80, 40).pixels().count(), 6);
+    }
+}
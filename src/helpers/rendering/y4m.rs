@@ -0,0 +1,197 @@
+use std::{
+    fs::File,
+    io::{BufWriter, Write},
+    path::Path,
+};
+
+use anyhow::{ensure, Context, Result};
+use imgref::ImgVec;
+use rgb::RGBA8;
+
+use super::common::{frame_channel, FrameProgress, FrameReceiver};
+
+/// The frame rate of the video. Input frames get resampled to it,
+/// frames that are shown shorter than a video frame may get dropped.
+const FRAME_RATE: f64 = 60.0;
+
+pub struct Y4mWriter {
+    sleep_time: f64,
+    receiver: FrameReceiver,
+}
+
+/// Converts an RGBA frame to the planes of a full resolution (4:4:4) YCbCr frame,
+/// using BT.601 limited range. Transparent pixels get blended onto black.
+fn frame_to_yuv(frame: &ImgVec<RGBA8>) -> Vec<u8> {
+    let num_pixels = frame.width() * frame.height();
+    let mut planes = vec![0u8; 3 * num_pixels];
+    let (y_plane, chroma_planes) = planes.split_at_mut(num_pixels);
+    let (u_plane, v_plane) = chroma_planes.split_at_mut(num_pixels);
+
+    for (i, pixel) in frame.pixels().enumerate() {
+        let alpha = pixel.a as f64 / 255.0;
+        let (r, g, b) = (
+            pixel.r as f64 * alpha,
+            pixel.g as f64 * alpha,
+            pixel.b as f64 * alpha,
+        );
+        let y = 16.0 + (65.481 * r + 128.553 * g + 24.966 * b) / 255.0;
+        let u = 128.0 + (-37.797 * r - 74.203 * g + 112.0 * b) / 255.0;
+        let v = 128.0 + (112.0 * r - 93.786 * g - 18.214 * b) / 255.0;
+        y_plane[i] = y.round().clamp(0.0, 255.0) as u8;
+        u_plane[i] = u.round().clamp(0.0, 255.0) as u8;
+        v_plane[i] = v.round().clamp(0.0, 255.0) as u8;
+    }
+
+    planes
+}
+
+struct Y4mStream {
+    output: BufWriter<File>,
+    dimensions: (usize, usize),
+    frames_written: usize,
+}
+
+impl Y4mStream {
+    fn new(filename: &Path, width: usize, height: usize) -> Result<Self> {
+        let mut output = BufWriter::new(
+            File::create(filename)
+                .with_context(|| format!("Unable to create '{}'", filename.display()))?,
+        );
+        writeln!(
+            output,
+            "YUV4MPEG2 W{} H{} F{}:1 Ip A1:1 C444",
+            width, height, FRAME_RATE
+        )?;
+        Ok(Self {
+            output,
+            dimensions: (width, height),
+            frames_written: 0,
+        })
+    }
+
+    /// Repeats the frame until the video reaches the given duration.
+    fn write_until(&mut self, yuv: &[u8], end_time: f64) -> Result<()> {
+        let end_frame = (end_time * FRAME_RATE).round() as usize;
+        while self.frames_written < end_frame {
+            self.output.write_all(b"FRAME\n")?;
+            self.output.write_all(yuv)?;
+            self.frames_written += 1;
+        }
+        Ok(())
+    }
+}
+
+impl super::Writer for Y4mWriter {
+    fn write(self: Box<Self>, filename: &Path, frame_count_hint: Option<usize>) -> Result<()> {
+        let progress = FrameProgress::new(frame_count_hint);
+
+        let mut stream: Option<Y4mStream> = None;
+        // The previous frame, as it has to be repeated until the next one starts
+        let mut previous: Option<(Vec<u8>, f64)> = None;
+        let mut start_time = 0.0;
+
+        for (frame, timestamp) in self.receiver.iter() {
+            let stream = match &mut stream {
+                Some(stream) => stream,
+                None => {
+                    start_time = timestamp;
+                    stream.insert(Y4mStream::new(filename, frame.width(), frame.height())?)
+                }
+            };
+            ensure!(
+                stream.dimensions == (frame.width(), frame.height()),
+                "All frames of a Y4M video must have the same size!"
+            );
+
+            if let Some((yuv, _)) = &previous {
+                stream.write_until(yuv, timestamp - start_time)?;
+            }
+            previous = Some((frame_to_yuv(&frame), timestamp));
+            progress.inc();
+        }
+
+        if let (Some(mut stream), Some((yuv, timestamp))) = (stream, previous) {
+            stream.write_until(&yuv, timestamp - start_time + self.sleep_time)?;
+            // Even a single frame without sleep time has to produce a video
+            if stream.frames_written == 0 {
+                stream.write_until(&yuv, 1.0 / FRAME_RATE)?;
+            }
+            stream.output.flush()?;
+        }
+
+        progress.finish();
+        Ok(())
+    }
+}
+
+pub fn create_y4m(sleep_time: f64) -> Result<(impl super::Collector + Send, impl super::Writer)> {
+    let (collector, receiver) = frame_channel();
+    Ok((
+        collector,
+        Y4mWriter {
+            receiver,
+            sleep_time,
+        },
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{super::Collector, super::Writer, *};
+    use crate::helpers::test_dir::TestDir;
+
+    /// Renders 2x2 frames at the given timestamps and returns the number of video frames.
+    fn num_video_frames(timestamps: &[f64], sleep_time: f64) -> usize {
+        let dir = TestDir::new("y4m");
+        let filename = dir.join("video.y4m");
+
+        let (mut collector, writer) = create_y4m(sleep_time).unwrap();
+        for &timestamp in timestamps {
+            let frame = ImgVec::new(vec![RGBA8::new(255, 0, 0, 255); 4], 2, 2);
+            collector.add_frame(frame, timestamp).unwrap();
+        }
+        drop(collector);
+        Box::new(writer)
+            .write(&filename, Some(timestamps.len()))
+            .unwrap();
+
+        let data = std::fs::read(&filename).unwrap();
+        let header_end = data.iter().position(|&byte| byte == b'\n').unwrap() + 1;
+        assert_eq!(&data[..header_end], b"YUV4MPEG2 W2 H2 F60:1 Ip A1:1 C444\n");
+
+        // Every frame has three planes of four pixels
+        let chunks = data[header_end..].chunks(b"FRAME\n".len() + 3 * 4);
+        assert!(chunks.clone().all(|chunk| chunk.starts_with(b"FRAME\n")));
+        chunks.count()
+    }
+
+    #[test]
+    fn resamples_to_frame_rate() {
+        // Frames shorter than a video frame get dropped
+        assert_eq!(num_video_frames(&[0.0, 0.005], 0.005), 1);
+        // Frames exactly one video frame long
+        assert_eq!(num_video_frames(&[0.0, 1.0 / 60.0], 1.0 / 60.0), 2);
+        // Longer frames get repeated
+        assert_eq!(num_video_frames(&[0.0, 0.05], 0.05), 6);
+        // The last frame is shown for the sleep time, relative to the first frame
+        assert_eq!(num_video_frames(&[1.0, 1.5], 0.25), 45);
+        assert_eq!(num_video_frames(&[0.0], 0.5), 30);
+        // A single frame always produces a video
+        assert_eq!(num_video_frames(&[0.0], 0.0), 1);
+    }
+
+    #[test]
+    fn works() {
+        let pixels = vec![
+            RGBA8::new(0, 0, 0, 255),
+            RGBA8::new(255, 255, 255, 255),
+            RGBA8::new(255, 0, 0, 255),
+            RGBA8::new(255, 255, 255, 0),
+        ];
+        let yuv = frame_to_yuv(&ImgVec::new(pixels, 2, 2));
+        assert_eq!(
+            yuv,
+            vec![16, 235, 81, 16, 128, 128, 90, 128, 128, 128, 240, 128]
+        );
+    }
+}
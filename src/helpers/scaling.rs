@@ -0,0 +1,158 @@
+use std::{fmt::Write, time::Duration};
+
+use anyhow::Result;
+
+use super::input_generation::Generator;
+
+pub struct Measurement {
+    pub size: usize,
+    pub time: Duration,
+}
+
+/// Fits `time = factor * size^exponent` through the measurements,
+/// via a least squares fit in log-log space.
+///
+/// Returns `(exponent, factor)`.
+pub fn fit_power_law(measurements: &[Measurement]) -> Option<(f64, f64)> {
+    let points = measurements
+        .iter()
+        .filter(|m| m.size > 0 && !m.time.is_zero())
+        .map(|m| ((m.size as f64).ln(), m.time.as_secs_f64().ln()))
+        .collect::<Vec<_>>();
+
+    if points.len() < 2 {
+        return None;
+    }
+
+    let n = points.len() as f64;
+    let mean_x = points.iter().map(|(x, _)| x).sum::<f64>() / n;
+    let mean_y = points.iter().map(|(_, y)| y).sum::<f64>() / n;
+    let covariance = points
+        .iter()
+        .map(|(x, y)| (x - mean_x) * (y - mean_y))
+        .sum::<f64>();
+    let variance = points
+        .iter()
+        .map(|(x, _)| (x - mean_x).powi(2))
+        .sum::<f64>();
+
+    if variance == 0.0 {
+        return None;
+    }
+
+    let exponent = covariance / variance;
+    let factor = (mean_y - exponent * mean_x).exp();
+    Some((exponent, factor))
+}
+
+/// Times the solver on generated inputs, doubling the size every step.
+///
+/// Stops early once a single run exceeds `time_limit`.
+/// Every size is run repeatedly and the fastest run is taken, to reduce noise.
+pub fn measure_scaling<F>(
+    generator: &Generator,
+    steps: usize,
+    time_limit: Duration,
+    mut solve: F,
+) -> Result<Vec<Measurement>>
+where
+    F: FnMut(&str) -> Result<Duration>,
+{
+    let mut measurements = vec![];
+
+    for step in 0..steps {
+        let size = generator.start_size << step;
+        let input_data = (generator.generate)(size, step as u64);
+
+        let mut total = Duration::ZERO;
+        let mut fastest = Duration::MAX;
+        for _ in 0..10 {
+            let time = solve(&input_data)?;
+            total += time;
+            fastest = std::cmp::min(fastest, time);
+            if total >= Duration::from_millis(200) {
+                break;
+            }
+        }
+
+        println!(
+            "   ... size {}: {:.3} ms",
+            size,
+            fastest.as_secs_f64() * 1000.0
+        );
+        measurements.push(Measurement {
+            size,
+            time: fastest,
+        });
+
+        if fastest > time_limit {
+            println!("   ... exceeded time limit, stopping.");
+            break;
+        }
+    }
+
+    Ok(measurements)
+}
+
+pub fn format_report(generator: &Generator, measurements: &[Measurement]) -> String {
+    const BAR_WIDTH: f64 = 30.0;
+
+    let mut report = String::new();
+    writeln!(report, "size: {}", generator.size_description).unwrap();
+    writeln!(report).unwrap();
+
+    let log_times = measurements
+        .iter()
+        .map(|m| m.time.as_secs_f64().max(1e-9).ln())
+        .collect::<Vec<_>>();
+    let min_log_time = log_times.iter().cloned().fold(f64::INFINITY, f64::min);
+    let max_log_time = log_times.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+    let log_range = (max_log_time - min_log_time).max(f64::EPSILON);
+
+    for (measurement, log_time) in measurements.iter().zip(log_times) {
+        let bar_length = 1 + ((log_time - min_log_time) / log_range * BAR_WIDTH).round() as usize;
+        writeln!(
+            report,
+            "{:>8} {:>12.3} ms  {}",
+            measurement.size,
+            measurement.time.as_secs_f64() * 1000.0,
+            "█".repeat(bar_length)
+        )
+        .unwrap();
+    }
+    writeln!(report).unwrap();
+
+    match fit_power_law(measurements) {
+        Some((exponent, factor)) => write!(
+            report,
+            "Fitted: time ≈ {:.3e} s · size^{:.2}",
+            factor, exponent
+        ),
+        None => write!(report, "Not enough measurements to fit a power law."),
+    }
+    .unwrap();
+
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn works() {
+        let measurements = [1, 2, 4, 8, 16]
+            .into_iter()
+            .map(|size| Measurement {
+                size,
+                time: Duration::from_secs_f64(0.003 * (size as f64).powf(2.5)),
+            })
+            .collect::<Vec<_>>();
+
+        let (exponent, factor) = fit_power_law(&measurements).unwrap();
+        assert!((exponent - 2.5).abs() < 1e-6);
+        assert!((factor - 0.003).abs() < 1e-6);
+
+        assert_eq!(fit_power_law(&measurements[..1]), None);
+    }
+}
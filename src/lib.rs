@@ -0,0 +1,57 @@
+use anyhow::{anyhow, Result};
+
+pub mod helpers;
+
+// DAILY: Add new solutions here
+solutions! {
+    (day01, task1, task2)
+    (day02, task1, task2)
+    (day03, task1, task2)
+    (day04, task1, task2)
+    (day05, task1, task2)
+    (day06, task1, task2)
+    (day07, task1, task2)
+    (day08, task1, task2)
+    (day09, task1, task2)
+    (day10, task1, task2)
+    (day11, task1, task2)
+    (day12, task1, task2)
+    (day13, task1, task2)
+    (day14, task1, task2)
+    (day15, task1, task2)
+    (day16, task1, task2)
+    (day17, task1, task2)
+    (day18, task1, task2)
+    (day19, task1, task2)
+    (day20, task1, task2)
+    (day21, task1, task2)
+    (day22, task1, task2)
+    (day23, task1, task2)
+    (day24, task1, task2)
+    (day25, task1)
+}
+
+// DAILY: Add new reference solutions here
+reworked_solutions! {
+    (day01, task1, task2)
+    (day08, task1, task2)
+    (day12, task1, task2)
+    (day16, task1, task2)
+    (day17, task1, task2)
+    (day22, task1, task2)
+}
+
+// DAILY: Add new renderers here
+renderers! {
+    (day05, task1, task2)
+    (day09, task2)
+    (day11, task2)
+    (day12, task1, task2)
+    (day13, task1, task2)
+    (day15, task1, task2)
+    (day16, task1, task2)
+    (day17, task1, task2)
+    (day20, task1, task2)
+    (day23, task1, task2)
+    (day25, task1)
+}
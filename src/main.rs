@@ -1,55 +1,22 @@
-use anyhow::{anyhow, Result};
+use anyhow::{anyhow, Context, Result};
 use clap::Parser;
-use std::{fs, path::PathBuf};
-
-mod helpers;
-
-// DAILY: Add new solutions here
-solutions! {
-    (day01, task1, task2)
-    (day02, task1, task2)
-    (day03, task1, task2)
-    (day04, task1, task2)
-    (day05, task1, task2)
-    (day06, task1, task2)
-    (day07, task1, task2)
-    (day08, task1, task2)
-    (day09, task1, task2)
-    (day10, task1, task2)
-    (day11, task1, task2)
-    (day12, task1, task2)
-    (day13, task1, task2)
-    (day14, task1, task2)
-    (day15, task1, task2)
-    (day16, task1, task2)
-    (day17, task1, task2)
-    (day18, task1, task2)
-    (day19, task1, task2)
-    (day20, task1, task2)
-    (day21, task1, task2)
-    (day22, task1, task2)
-    (day23, task1, task2)
-    (day24, task1, task2)
-    (day25, task1)
-}
+use std::{fs, path::PathBuf, time::Duration};
 
-// DAILY: Add new reference solutions here
-reworked_solutions! {
-    (day01, task1, task2)
-    (day08, task1, task2)
-    (day12, task1, task2)
-    (day16, task1, task2)
-    (day17, task1, task2)
-    (day22, task1, task2)
-}
-
-// DAILY: Add new renderers here
-renderers! {
-    (day05, task1, task2)
-    (day11, task2)
-    (day15, task1, task2)
-    (day25, task1)
-}
+use advent_of_code_2021::{
+    helpers::{
+        benchmarking::SolutionOutput,
+        example_extraction::write_examples,
+        input_generation::generator,
+        input_manager::{HttpBackend, InputManager},
+        input_normalization::normalize_input,
+        rendering::{
+            resize::{FrameSize, Viewport},
+            RenderFormat, RenderOptions,
+        },
+        scaling::{format_report, measure_scaling},
+    },
+    run_renderer, run_reworked_solutions, run_solution,
+};
 
 /// This is a solver for Advent of Code 2021 tasks.
 #[derive(Parser)]
@@ -63,9 +30,10 @@ pub struct Options {
     #[clap()]
     pub task: u8,
 
-    /// The path to the challenge input data
+    /// The path to the challenge input data.
+    /// If omitted, the input is fetched into 'input_data' (requires AOC_SESSION)
     #[clap()]
-    pub data: PathBuf,
+    pub data: Option<PathBuf>,
 
     /// Run the reworked solution of mine
     #[clap(short, long)]
@@ -74,12 +42,101 @@ pub struct Options {
     /// Render the task visually, if available
     #[clap(long)]
     pub render: bool,
+
+    /// Where to write rendering artifacts to, either a directory or a file name.
+    /// Defaults to the current directory
+    #[clap(long)]
+    pub output: Option<PathBuf>,
+
+    /// The format of rendered animations.
+    /// Defaults to the format preferred by the renderer
+    #[clap(long, possible_values = RenderFormat::ALL)]
+    pub render_format: Option<RenderFormat>,
+
+    /// Merge identical consecutive frames of rendered animations
+    #[clap(long)]
+    pub dedup_frames: bool,
+
+    /// Only keep animation frames in which at least this percentage of pixels changed
+    #[clap(long, value_name = "PERCENT")]
+    pub min_frame_change: Option<f64>,
+
+    /// Annotate rendered frames with captions and legends
+    #[clap(long)]
+    pub captions: bool,
+
+    /// Fit rendered frames into this size, e.g. '800x600'
+    #[clap(long, value_name = "WIDTHxHEIGHT")]
+    pub frame_size: Option<FrameSize>,
+
+    /// Only render a section of the grid, in grid cells, e.g. '0,0,100x100'
+    #[clap(long, value_name = "X,Y,WIDTHxHEIGHT")]
+    pub viewport: Option<Viewport>,
+
+    /// Treat the data file as a saved puzzle description page and
+    /// extract its examples into 'input_data'
+    #[clap(long)]
+    pub extract_examples: bool,
+
+    /// Measure how the solver scales with generated inputs of increasing size
+    #[clap(long)]
+    pub scale: bool,
+
+    /// The number of input sizes to measure in '--scale' mode
+    #[clap(long, default_value = "6")]
+    pub scale_steps: usize,
+}
+
+fn run_solver(reworked: bool, day: u8, task: u8, data: &str) -> Result<SolutionOutput> {
+    match reworked {
+        true => run_reworked_solutions(day, task, data),
+        false => run_solution(day, task, data),
+    }
 }
 
 fn main() -> Result<()> {
     let opts = Options::parse();
 
-    let input_file_path = opts.data;
+    if opts.extract_examples {
+        let html_file = opts
+            .data
+            .ok_or_else(|| anyhow!("Extracting examples requires a puzzle description file!"))?;
+        let (written, test_table) = write_examples(opts.day, &html_file, "input_data".as_ref())?;
+        println!("─ Extracted examples: ──────────────────────────");
+        for path in written {
+            println!("{}", path.display());
+        }
+        println!("─ Suggested tests: ─────────────────────────────");
+        println!("{}", test_table);
+        println!("────────────────────────────────────────────────");
+        return Ok(());
+    }
+
+    if opts.scale {
+        let generator = generator(opts.day)
+            .ok_or_else(|| anyhow!("No input generator available for day {}!", opts.day))?;
+        println!(
+            "Measuring scaling of {}solver day{:0>2}::task{} ...",
+            if opts.reworked { "reworked " } else { "" },
+            opts.day,
+            opts.task
+        );
+        let measurements = measure_scaling(
+            &generator,
+            opts.scale_steps,
+            Duration::from_secs(10),
+            |data| Ok(run_solver(opts.reworked, opts.day, opts.task, data)?.calculation_time),
+        )?;
+        println!("─ Scaling: ─────────────────────────────────────");
+        println!("{}", format_report(&generator, &measurements));
+        println!("────────────────────────────────────────────────");
+        return Ok(());
+    }
+
+    let input_file_path = match opts.data {
+        Some(data) => data,
+        None => InputManager::new(2021, "input_data", HttpBackend::from_env()).get(opts.day)?,
+    };
     let data = fs::read_to_string(&input_file_path).map_err(|err| {
         anyhow!(
             "Unable to open '{}': {}",
@@ -88,23 +145,48 @@ fn main() -> Result<()> {
         )
     })?;
 
+    let (data, normalization_report) = normalize_input(&data);
+    if !normalization_report.is_unchanged() {
+        println!("Normalized input data: {}", normalization_report);
+    }
+
     if opts.render {
-        let artifacts = run_renderer(opts.day, opts.task, &data)?;
+        let render_options = RenderOptions {
+            output: opts.output,
+            format: opts.render_format,
+            dedup_frames: opts.dedup_frames,
+            min_frame_change: opts.min_frame_change.map(|percent| percent / 100.0),
+            captions: opts.captions,
+            frame_size: opts.frame_size,
+            viewport: opts.viewport,
+        };
+        render_options
+            .create_output_directory()
+            .context("Unable to create output directory")?;
+        let artifacts = run_renderer(opts.day, opts.task, &data, &render_options)?;
         println!("─ Rendering artifacts: ─────────────────────────");
         for artifact in artifacts {
-            println!("{}", artifact);
+            println!("{}", artifact.display());
         }
         println!("────────────────────────────────────────────────");
         return Ok(());
     }
 
-    let result = match opts.reworked {
-        true => run_reworked_solutions(opts.day, opts.task, &data)?,
-        false => run_solution(opts.day, opts.task, &data)?,
-    };
+    println!(
+        "Running {}solver day{:0>2}::task{} ...",
+        if opts.reworked { "reworked " } else { "" },
+        opts.day,
+        opts.task
+    );
+    let solution = run_solver(opts.reworked, opts.day, opts.task, &data)?;
+    println!("   ... parse input: {} ms", solution.parse_time.as_millis());
+    println!(
+        "   ... calculate: {} ms",
+        solution.calculation_time.as_millis()
+    );
 
     println!("─ Result: ──────────────────────────────────────");
-    println!("{}", result);
+    println!("{}", solution.result);
     println!("────────────────────────────────────────────────");
 
     Ok(())
@@ -1,42 +1,113 @@
-use image::ImageBuffer;
-
-use crate::solutions::day05::{VentLine, VentMap};
-
-fn write_to_image(vent_map: &VentMap, name: &str) -> String {
-    let image = ImageBuffer::from_fn(vent_map.width as u32, vent_map.height as u32, |x, y| {
-        let value = vent_map.get(x as usize, y as usize);
-        let luma = 255 - 50 * value;
-        if luma > 255 {
-            image::Luma([255u8])
-        } else if luma < 0 {
-            image::Luma([0u8])
-        } else {
-            image::Luma([luma as u8])
-        }
+use std::path::PathBuf;
+
+use anyhow::Result;
+use ndarray::Array2;
+use rgb::RGBA8;
+
+use crate::{
+    helpers::rendering::{
+        colormap::{colorize_in_range, Colormap, ValueRange},
+        svg::{Style, Svg},
+        write_png, RenderContext,
+    },
+    solutions::day05::{VentLine, VentMap},
+};
+
+fn write_to_image(vent_map: &VentMap, context: &RenderContext) -> Result<PathBuf> {
+    let map = Array2::from_shape_fn((vent_map.height, vent_map.width), |(y, x)| {
+        vent_map.get(x, y)
     });
 
-    let output_path = std::env::current_dir().unwrap().join(name);
+    // White background, overlapping vents get darker
+    let range = ValueRange::of(map.iter().copied()).reversed();
+    let colors = colorize_in_range(&map, Colormap::Grayscale, range);
+    let image = context.options().frame_layout().map_to_image(&colors, 1)?;
 
-    image.save(&output_path).unwrap();
+    let output_path = context.output_path(None, "png")?;
+    write_png(&output_path, &image)?;
 
-    output_path.into_os_string().into_string().unwrap()
+    Ok(output_path)
 }
 
-pub fn task1(input_data: &[VentLine]) -> Vec<String> {
+fn line_points(line: &VentLine) -> impl Iterator<Item = (usize, usize)> + '_ {
+    let dx = (line.end.x as i64 - line.start.x as i64).signum();
+    let dy = (line.end.y as i64 - line.start.y as i64).signum();
+    let length = std::cmp::max(
+        (line.end.x as i64 - line.start.x as i64).abs(),
+        (line.end.y as i64 - line.start.y as i64).abs(),
+    );
+
+    (0..=length).map(move |step| {
+        (
+            (line.start.x as i64 + step * dx) as usize,
+            (line.start.y as i64 + step * dy) as usize,
+        )
+    })
+}
+
+/// Draws every vent line, colored by the highest number of overlapping lines along it.
+fn write_to_svg<'a>(
+    lines: impl Iterator<Item = &'a VentLine>,
+    vent_map: &VentMap,
+    context: &RenderContext,
+) -> Result<PathBuf> {
+    let mut lines = lines
+        .map(|line| {
+            let overlap = line_points(line)
+                .map(|(x, y)| vent_map.get(x, y))
+                .max()
+                .unwrap_or(1);
+            (line, overlap)
+        })
+        .collect::<Vec<_>>();
+    // Draw the lines with the most overlaps on top
+    lines.sort_by_key(|&(_, overlap)| overlap);
+
+    let range = ValueRange::of(lines.iter().map(|&(_, overlap)| overlap));
+    let (width, height) = (vent_map.width as f64, vent_map.height as f64);
+    // Tiny maps, like the examples, would be barely visible otherwise
+    let mut svg = Svg::new(width, height).scaled((600.0 / width.max(height)).max(1.0));
+    svg.background(RGBA8::new(255, 255, 255, 255));
+    for (line, overlap) in lines {
+        let color = Colormap::Viridis.color_in_range(overlap, range);
+        svg.line(
+            (line.start.x as f64 + 0.5, line.start.y as f64 + 0.5),
+            (line.end.x as f64 + 0.5, line.end.y as f64 + 0.5),
+            Style::stroke(color, 1.0),
+        );
+    }
+
+    let output_path = context.artifact_path(None, "svg");
+    svg.save(&output_path)?;
+
+    Ok(output_path)
+}
+
+pub fn task1(input_data: &[VentLine], context: &RenderContext) -> Result<Vec<PathBuf>> {
     let mut vent_map = VentMap::new_auto_bounds(input_data);
 
     for line in input_data {
         vent_map.render_straight_line(line);
     }
 
-    vec![write_to_image(&vent_map, "day05_task1.png")]
+    let straight_lines = input_data
+        .iter()
+        .filter(|line| line.start.x == line.end.x || line.start.y == line.end.y);
+
+    Ok(vec![
+        write_to_image(&vent_map, context)?,
+        write_to_svg(straight_lines, &vent_map, context)?,
+    ])
 }
-pub fn task2(input_data: &[VentLine]) -> Vec<String> {
+pub fn task2(input_data: &[VentLine], context: &RenderContext) -> Result<Vec<PathBuf>> {
     let mut vent_map = VentMap::new_auto_bounds(input_data);
 
     for line in input_data {
         vent_map.render_line(line);
     }
 
-    vec![write_to_image(&vent_map, "day05_task2.png")]
+    Ok(vec![
+        write_to_image(&vent_map, context)?,
+        write_to_svg(input_data.iter(), &vent_map, context)?,
+    ])
 }
@@ -0,0 +1,173 @@
+use std::{collections::HashSet, path::PathBuf};
+
+use anyhow::Result;
+use imgref::ImgVec;
+use ndarray::Array2;
+use rgb::RGBA8;
+
+use crate::{
+    helpers::{
+        input_parsing::parse_as_2d_matrix,
+        rendering::{
+            colormap::{mix, Colormap, ValueRange},
+            overlay::draw_caption,
+            render_animation, Collector, ErrorLatch, RenderContext, RenderFormat, RenderOptions,
+            ToColor,
+        },
+    },
+    solutions::day09::{flood_fill, is_minimum},
+};
+
+#[derive(Clone)]
+struct Location {
+    height: i64,
+    low_point: bool,
+    basin: Option<usize>,
+    faded: bool,
+}
+
+const HEIGHT_RANGE: ValueRange = ValueRange { min: 0.0, max: 9.0 };
+
+impl ToColor for Location {
+    fn to_color(&self) -> RGBA8 {
+        // Light to dark gray, the basin borders are darkest
+        let height = HEIGHT_RANGE.normalize(self.height as f64);
+        let base = Colormap::Grayscale.color(0.9 - 0.7 * height);
+
+        if self.low_point && !self.faded {
+            return RGBA8::new(255, 0, 0, 255);
+        }
+        match self.basin {
+            Some(basin) if !self.faded => mix(base, Colormap::categorical(basin as i64), 0.7),
+            _ => base,
+        }
+    }
+}
+
+fn to_image(
+    map: &Array2<Location>,
+    scale: usize,
+    options: &RenderOptions,
+    caption: impl FnOnce() -> String,
+) -> Result<ImgVec<RGBA8>> {
+    let mut image = options.frame_layout().map_to_image(map, scale)?;
+    if options.captions {
+        let caption = caption();
+        draw_caption(&mut image, &caption);
+    }
+    Ok(image)
+}
+
+fn generate_images(
+    mut collector: impl Collector,
+    heights: &Array2<i64>,
+    speedup: usize,
+    options: &RenderOptions,
+) -> Result<()> {
+    let time_step = 1.0 / 30.0;
+    let scale = (600 / heights.nrows().max(heights.ncols())).max(1);
+
+    let mut map = heights.mapv(|height| Location {
+        height,
+        low_point: false,
+        basin: None,
+        faded: false,
+    });
+
+    collector.add_frame(
+        to_image(&map, scale, options, || "LOW POINTS 0".to_string())?,
+        time_step * collector.get_num_frames() as f64,
+    )?;
+
+    // Discover the low points, one row per frame
+    let mut low_points = vec![];
+    for row in 0..heights.nrows() {
+        for column in 0..heights.ncols() {
+            // Low points on a plateau of borders don't form a basin
+            if heights[(row, column)] != 9 && is_minimum((row, column), heights) {
+                map[(row, column)].low_point = true;
+                low_points.push((row, column));
+            }
+        }
+        collector.add_frame(
+            to_image(&map, scale, options, || {
+                format!("LOW POINTS {}", low_points.len())
+            })?,
+            time_step * collector.get_num_frames() as f64,
+        )?;
+    }
+
+    // Grow the basins
+    let mut basin_sizes = vec![];
+    for (basin_id, &low_point) in low_points.iter().enumerate() {
+        let mut basin = HashSet::new();
+        let mut skipped = 0;
+        let mut latch = ErrorLatch::new();
+        flood_fill(heights, &mut basin, low_point, &mut |index| {
+            map[index].basin = Some(basin_id);
+            skipped += 1;
+            if skipped >= speedup {
+                skipped = 0;
+                latch.run(|| {
+                    collector.add_frame(
+                        to_image(&map, scale, options, || format!("BASINS {}", basin_id + 1))?,
+                        time_step * collector.get_num_frames() as f64,
+                    )
+                });
+            }
+        });
+        latch.into_result()?;
+        basin_sizes.push((basin_id, basin.len()));
+    }
+    collector.add_frame(
+        to_image(&map, scale, options, || {
+            format!("BASINS {}", low_points.len())
+        })?,
+        time_step * collector.get_num_frames() as f64,
+    )?;
+
+    // Highlight the three largest basins, recolored by rank to keep them distinct
+    basin_sizes.sort_by_key(|&(_, size)| std::cmp::Reverse(size));
+    let largest = basin_sizes.iter().take(3).collect::<Vec<_>>();
+    for location in map.iter_mut() {
+        let rank = location
+            .basin
+            .and_then(|basin| largest.iter().position(|&&(id, _)| id == basin));
+        location.basin = rank.or(location.basin);
+        location.faded = rank.is_none();
+    }
+    collector.add_frame(
+        to_image(&map, scale, options, || {
+            format!(
+                "LARGEST {}\nPRODUCT {}",
+                largest
+                    .iter()
+                    .map(|(_, size)| size.to_string())
+                    .collect::<Vec<_>>()
+                    .join(", "),
+                largest.iter().map(|(_, size)| size).product::<usize>()
+            )
+        })?,
+        time_step * collector.get_num_frames() as f64,
+    )?;
+
+    Ok(())
+}
+
+pub fn task2(input_data: &str, context: &RenderContext) -> Result<Vec<PathBuf>> {
+    let speedup = 10;
+    let heights = parse_as_2d_matrix::<i64>(input_data)?;
+
+    // Every field except the basin borders gets visited exactly once
+    let num_visited = heights.iter().filter(|&&height| height != 9).count();
+    let num_frames = 1 + heights.nrows() + num_visited / speedup + 2;
+
+    let options = context.options().clone();
+    render_animation(
+        context,
+        RenderFormat::Gif,
+        3.0,
+        Some(num_frames),
+        move |collector| generate_images(collector, &heights, speedup, &options),
+    )
+}
@@ -1,85 +1,103 @@
-use std::fs::File;
+use std::path::PathBuf;
 
-use gifski;
+use anyhow::Result;
 use imgref::ImgVec;
-use ndarray::{Array2, Axis};
+use ndarray::Array2;
 use rgb::RGBA8;
 
-use crate::solutions::day11::update_map;
-
-fn map_to_image(map: &Array2<u8>, scale: usize) -> ImgVec<RGBA8> {
-    let mut pixels = vec![];
+use crate::{
+    helpers::rendering::{
+        colormap::{Colormap, ValueRange},
+        overlay::draw_caption,
+        render_animation, Collector, RenderContext, RenderFormat, RenderOptions, ToColor,
+    },
+    solutions::day11::update_map,
+};
+
+struct Octopus {
+    energy: u8,
+}
 
-    for row in map.axis_iter(Axis(0)) {
-        let mut row_pixels = vec![];
-        for &value in row {
-            let pixel = if value == 0 {
-                RGBA8::new(255, 255, 128, 255)
-            } else {
-                let intensity = 170 - value * 12;
-                RGBA8::new(intensity, intensity, intensity, 255)
-            };
+const ENERGY_RANGE: ValueRange = ValueRange { min: 1.0, max: 9.0 };
 
-            for _ in 0..scale {
-                row_pixels.push(pixel);
-            }
-        }
-        for _ in 0..scale {
-            pixels.extend(row_pixels.iter());
+impl ToColor for Octopus {
+    fn to_color(&self) -> RGBA8 {
+        if self.energy == 0 {
+            RGBA8::new(255, 255, 128, 255)
+        } else {
+            // Medium to dark gray, darker with more energy
+            let energy = ENERGY_RANGE.normalize(self.energy as f64);
+            Colormap::Grayscale.color(0.65 - 0.4 * energy)
         }
     }
+}
 
-    ImgVec::<RGBA8>::new(pixels, map.dim().1 * scale, map.dim().0 * scale)
+fn to_image(
+    map: &Array2<u8>,
+    scale: usize,
+    options: &RenderOptions,
+    caption: impl FnOnce() -> String,
+) -> Result<ImgVec<RGBA8>> {
+    let octopuses = map.mapv(|energy| Octopus { energy });
+    let mut image = options.frame_layout().map_to_image(&octopuses, scale)?;
+    if options.captions {
+        let caption = caption();
+        draw_caption(&mut image, &caption);
+    }
+    Ok(image)
 }
 
-pub fn task2(input_data: &Array2<u8>) -> Vec<String> {
+fn generate_images(
+    mut collector: impl Collector,
+    input_data: &Array2<u8>,
+    final_frames: usize,
+    options: &RenderOptions,
+) -> Result<()> {
     let scale = 20;
     let time_step = 1.0 / 10.0;
-    let final_frames = 100;
-
-    let (mut collector, writer) = gifski::new(gifski::Settings {
-        quality: 100,
-        fast: false,
-        repeat: gifski::Repeat::Infinite,
-        width: None,
-        height: None,
-    })
-    .unwrap();
-
-    let num_frames = crate::solutions::day11::task2(input_data);
 
     let mut map = input_data.clone();
-    let collector_thread = std::thread::spawn(move || {
-        let mut index = 0;
-
-        collector
-            .add_frame_rgba(index, map_to_image(&map, scale), index as f64 * time_step)
-            .unwrap();
-        index += 1;
-
-        while update_map(&mut map) != map.len() {
-            collector
-                .add_frame_rgba(index, map_to_image(&map, scale), index as f64 * time_step)
-                .unwrap();
-            index += 1;
-            //println!("Frame {} ...", index);
+    let mut step = 0;
+    let mut num_flashes = 0;
+    let caption = |step, num_flashes| move || format!("STEP {}\nFLASHES {}", step, num_flashes);
+
+    collector.add_frame(
+        to_image(&map, scale, options, caption(step, num_flashes))?,
+        time_step * collector.get_num_frames() as f64,
+    )?;
+
+    loop {
+        let flashes = update_map(&mut map);
+        step += 1;
+        num_flashes += flashes;
+        collector.add_frame(
+            to_image(&map, scale, options, caption(step, num_flashes))?,
+            time_step * collector.get_num_frames() as f64,
+        )?;
+        if flashes == map.len() {
+            break;
         }
+    }
 
-        for _ in 0..final_frames {
-            update_map(&mut map);
-            collector
-                .add_frame_rgba(index, map_to_image(&map, scale), index as f64 * time_step)
-                .unwrap();
-            index += 1;
-        }
-    });
+    for _ in 0..final_frames {
+        num_flashes += update_map(&mut map);
+        step += 1;
+        collector.add_frame(
+            to_image(&map, scale, options, caption(step, num_flashes))?,
+            time_step * collector.get_num_frames() as f64,
+        )?;
+    }
 
-    let filename = std::env::current_dir().unwrap().join("aoc2021_day11.gif");
-    let file = File::create(&filename).unwrap();
-    let mut progress = gifski::progress::ProgressBar::new((num_frames + final_frames) as u64);
-    writer.write(&file, &mut progress).unwrap();
+    Ok(())
+}
 
-    collector_thread.join().unwrap();
+pub fn task2(input_data: &Array2<u8>, context: &RenderContext) -> Result<Vec<PathBuf>> {
+    let final_frames = 100;
 
-    vec![filename.into_os_string().into_string().unwrap()]
+    // The number of steps is only known once the simulation finished
+    let input_data = input_data.clone();
+    let options = context.options().clone();
+    render_animation(context, RenderFormat::Gif, 0.1, None, move |collector| {
+        generate_images(collector, &input_data, final_frames, &options)
+    })
 }
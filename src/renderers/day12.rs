@@ -0,0 +1,120 @@
+use std::{
+    collections::{HashMap, HashSet},
+    path::PathBuf,
+};
+
+use anyhow::{anyhow, Result};
+use rgb::RGBA8;
+
+use crate::{
+    helpers::rendering::{
+        colormap::{Colormap, ValueRange},
+        dot::{color, DotGraph},
+        RenderContext,
+    },
+    solutions::day12::{
+        find_node, find_num_paths, find_num_paths_double_small, tunnel_counter, GraphNode,
+    },
+};
+
+/// Writes the cave system, with every tunnel labeled by how often the paths use it.
+fn write_dot(
+    graph: &[GraphNode],
+    num_paths: usize,
+    edge_counts: &HashMap<(usize, usize), usize>,
+    context: &RenderContext,
+) -> Result<PathBuf> {
+    let mut dot = DotGraph::new("caves", false);
+    let title = format!("{} paths", num_paths);
+    dot.defaults("graph", &[("label", &title), ("labelloc", "t")]);
+    dot.defaults("node", &[("style", "filled"), ("fontname", "monospace")]);
+    dot.defaults("edge", &[("fontname", "monospace")]);
+
+    for node in graph {
+        let (shape, fill) = match node.name.as_str() {
+            "start" | "end" => ("doublecircle", RGBA8::new(150, 220, 150, 255)),
+            _ if node.is_large => ("box", RGBA8::new(250, 190, 120, 255)),
+            _ => ("ellipse", RGBA8::new(180, 210, 250, 255)),
+        };
+        dot.node(&node.name, &[("shape", shape), ("fillcolor", &color(fill))]);
+    }
+
+    // Path counts span many magnitudes, so scale the edge width logarithmically
+    let range = ValueRange::of(edge_counts.values().map(|&count| (count as f64).ln_1p()));
+    for (id, node) in graph.iter().enumerate() {
+        for &neighbor in node.neighbors.iter().filter(|&&neighbor| neighbor > id) {
+            let count = edge_counts.get(&(id, neighbor)).copied().unwrap_or(0);
+            let label = count.to_string();
+            if count == 0 {
+                let attributes = [("label", label.as_str()), ("style", "dashed")];
+                dot.edge(&node.name, &graph[neighbor].name, &attributes);
+            } else {
+                let weight = range.normalize((count as f64).ln_1p());
+                let penwidth = format!("{:.2}", 1.0 + 5.0 * weight);
+                let edge_color = color(Colormap::Viridis.color(weight));
+                let attributes = [
+                    ("label", label.as_str()),
+                    ("penwidth", penwidth.as_str()),
+                    ("color", edge_color.as_str()),
+                ];
+                dot.edge(&node.name, &graph[neighbor].name, &attributes);
+            }
+        }
+    }
+
+    let output_path = context.output_path(None, "dot")?;
+    dot.save(&output_path)?;
+    Ok(output_path)
+}
+
+fn start_and_end(graph: &[GraphNode]) -> Result<(usize, usize)> {
+    let start = find_node(graph, "start").ok_or_else(|| anyhow!("No 'start' cave"))?;
+    let end = find_node(graph, "end").ok_or_else(|| anyhow!("No 'end' cave"))?;
+    Ok((start, end))
+}
+
+pub fn task1(input_data: &[GraphNode], context: &RenderContext) -> Result<Vec<PathBuf>> {
+    let (start, end) = start_and_end(input_data)?;
+
+    let mut edge_counts = HashMap::new();
+    let num_paths = find_num_paths(
+        input_data,
+        start,
+        end,
+        &mut HashSet::new(),
+        &mut Vec::new(),
+        0,
+        &mut tunnel_counter(&mut edge_counts),
+    );
+
+    Ok(vec![write_dot(
+        input_data,
+        num_paths,
+        &edge_counts,
+        context,
+    )?])
+}
+
+pub fn task2(input_data: &[GraphNode], context: &RenderContext) -> Result<Vec<PathBuf>> {
+    let (start, end) = start_and_end(input_data)?;
+
+    let mut edge_counts = HashMap::new();
+    let num_paths = find_num_paths_double_small(
+        input_data,
+        start,
+        start,
+        end,
+        &mut HashSet::new(),
+        &mut Vec::new(),
+        false,
+        0,
+        &mut tunnel_counter(&mut edge_counts),
+    );
+
+    Ok(vec![write_dot(
+        input_data,
+        num_paths,
+        &edge_counts,
+        context,
+    )?])
+}
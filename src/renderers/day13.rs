@@ -0,0 +1,320 @@
+use std::{collections::HashSet, path::PathBuf};
+
+use anyhow::Result;
+use imgref::ImgVec;
+use ndarray::Array2;
+use rgb::RGBA8;
+
+use crate::{
+    helpers::rendering::{
+        map_to_image,
+        overlay::draw_caption,
+        render_animation,
+        resize::{resize, FrameSize},
+        svg::{Style, Svg},
+        Collector, RenderContext, RenderFormat, RenderOptions, ToColor,
+    },
+    solutions::day13::{fold_dots, Dot, Fold, PuzzleInput},
+};
+
+const FOLD_FRAMES: usize = 30;
+const FOLD_PAUSE: f64 = 0.5;
+
+#[derive(Clone, Copy)]
+enum PaperTile {
+    Paper,
+    Dot,
+    Seam,
+}
+
+impl ToColor for PaperTile {
+    fn to_color(&self) -> RGBA8 {
+        match self {
+            PaperTile::Paper => RGBA8::new(255, 255, 255, 255),
+            PaperTile::Dot => RGBA8::new(0, 0, 128, 255),
+            PaperTile::Seam => RGBA8::new(255, 0, 0, 255),
+        }
+    }
+}
+
+const BACKGROUND: RGBA8 = RGBA8::new(40, 40, 40, 255);
+
+/// Draws the paper with its dots, the upcoming fold line and a caption above the paper.
+fn paper_to_svg(dots: &HashSet<Dot>, size: (i64, i64), fold: Option<&Fold>, caption: &str) -> Svg {
+    let (width, height) = (size.0 as f64, size.1 as f64);
+    let text_size = width / 14.0;
+    let caption_height = text_size * 1.5;
+
+    let mut svg =
+        Svg::new(width, height + caption_height).scaled((600.0 / width.max(height)).max(1.0));
+    svg.background(RGBA8::new(255, 255, 255, 255));
+    svg.text(
+        (0.0, text_size),
+        caption,
+        text_size,
+        RGBA8::new(0, 0, 0, 255),
+    );
+
+    let to_paper = |x: f64, y: f64| (x, y + caption_height);
+    for dot in dots {
+        svg.point(
+            to_paper(dot.x as f64 + 0.5, dot.y as f64 + 0.5),
+            0.4,
+            RGBA8::new(0, 0, 128, 255),
+        );
+    }
+
+    let fold_style = Style::stroke(RGBA8::new(255, 0, 0, 255), 0.5).dashed(2.0);
+    match fold {
+        Some(Fold::X(seam)) => {
+            let x = *seam as f64 + 0.5;
+            svg.line(to_paper(x, 0.0), to_paper(x, height), fold_style);
+        }
+        Some(Fold::Y(seam)) => {
+            let y = *seam as f64 + 0.5;
+            svg.line(to_paper(0.0, y), to_paper(width, y), fold_style);
+        }
+        None => (),
+    }
+
+    svg
+}
+
+fn fold_caption(fold: &Fold) -> String {
+    match fold {
+        Fold::X(seam) => format!("fold along x={}", seam),
+        Fold::Y(seam) => format!("fold along y={}", seam),
+    }
+}
+
+/// Rasterizes the paper, with the given (possibly moving) dot positions,
+/// and fits it into the center of the canvas.
+fn paper_to_frame(
+    dots: &[(f64, f64)],
+    extent: (f64, f64),
+    fold: Option<&Fold>,
+    canvas: FrameSize,
+    caption: &str,
+    options: &RenderOptions,
+) -> ImgVec<RGBA8> {
+    let width = (extent.0.ceil() as usize).max(1);
+    let height = (extent.1.ceil() as usize).max(1);
+
+    let mut paper = Array2::from_elem((height, width), PaperTile::Paper);
+    match fold {
+        Some(Fold::X(seam)) if (*seam as usize) < width => {
+            paper.column_mut(*seam as usize).fill(PaperTile::Seam)
+        }
+        Some(Fold::Y(seam)) if (*seam as usize) < height => {
+            paper.row_mut(*seam as usize).fill(PaperTile::Seam)
+        }
+        _ => (),
+    }
+    for &(x, y) in dots {
+        if let Some(tile) = paper.get_mut((y.round() as usize, x.round() as usize)) {
+            *tile = PaperTile::Dot;
+        }
+    }
+
+    // Zoom in while the paper shrinks, so the final code fills the frame
+    let factor = f64::min(
+        canvas.width as f64 / width as f64,
+        canvas.height as f64 / height as f64,
+    );
+    let image = resize(
+        &map_to_image(&paper, 1),
+        ((width as f64 * factor).round() as usize).clamp(1, canvas.width),
+        ((height as f64 * factor).round() as usize).clamp(1, canvas.height),
+    );
+
+    let mut frame = ImgVec::new(
+        vec![BACKGROUND; canvas.width * canvas.height],
+        canvas.width,
+        canvas.height,
+    );
+    let left = (canvas.width - image.width()) / 2;
+    let top = (canvas.height - image.height()) / 2;
+    for (source, target) in image.rows().zip(frame.rows_mut().skip(top)) {
+        target[left..left + source.len()].copy_from_slice(source);
+    }
+
+    if options.captions {
+        draw_caption(&mut frame, &caption.to_uppercase());
+    }
+    frame
+}
+
+fn smoothstep(t: f64) -> f64 {
+    t * t * (3.0 - 2.0 * t)
+}
+
+fn generate_images(
+    mut collector: impl Collector,
+    input_data: &PuzzleInput,
+    num_folds: usize,
+    canvas: FrameSize,
+    options: &RenderOptions,
+) -> Result<()> {
+    let time_step = 1.0 / 30.0;
+    let mut time = 0.0;
+
+    let mut dots = input_data.dots.clone();
+    let mut size = paper_size(&dots);
+    let to_positions = |dots: &HashSet<Dot>| {
+        dots.iter()
+            .map(|dot| (dot.x as f64, dot.y as f64))
+            .collect::<Vec<_>>()
+    };
+
+    for fold in input_data.folds.iter().take(num_folds) {
+        let extent = (size.0 as f64, size.1 as f64);
+        let caption = fold_caption(fold);
+        collector.add_frame(
+            paper_to_frame(
+                &to_positions(&dots),
+                extent,
+                Some(fold),
+                canvas,
+                &caption,
+                options,
+            ),
+            time,
+        )?;
+        time += FOLD_PAUSE;
+
+        let folded_size = match fold {
+            Fold::X(seam) => (*seam, size.1),
+            Fold::Y(seam) => (size.0, *seam),
+        };
+        for frame_id in 1..=FOLD_FRAMES {
+            let progress = smoothstep(frame_id as f64 / FOLD_FRAMES as f64);
+            let lerp = |from: f64, to: f64| from + (to - from) * progress;
+
+            // Dots beyond the seam travel to their mirrored position
+            let positions = dots
+                .iter()
+                .map(|dot| {
+                    let (x, y) = (dot.x as f64, dot.y as f64);
+                    match fold {
+                        Fold::X(seam) if dot.x > *seam => (lerp(x, (2 * seam - dot.x) as f64), y),
+                        Fold::Y(seam) if dot.y > *seam => (x, lerp(y, (2 * seam - dot.y) as f64)),
+                        _ => (x, y),
+                    }
+                })
+                .collect::<Vec<_>>();
+            let extent = (
+                lerp(extent.0, folded_size.0 as f64),
+                lerp(extent.1, folded_size.1 as f64),
+            );
+
+            collector.add_frame(
+                paper_to_frame(&positions, extent, Some(fold), canvas, &caption, options),
+                time,
+            )?;
+            time += time_step;
+        }
+
+        dots = fold_dots(dots, fold);
+        size = folded_size;
+    }
+
+    collector.add_frame(
+        paper_to_frame(
+            &to_positions(&dots),
+            (size.0 as f64, size.1 as f64),
+            None,
+            canvas,
+            &format!("{} dots", dots.len()),
+            options,
+        ),
+        time,
+    )?;
+
+    Ok(())
+}
+
+fn paper_size(dots: &HashSet<Dot>) -> (i64, i64) {
+    dots.iter()
+        .fold((0, 0), |(x, y), dot| (x.max(dot.x + 1), y.max(dot.y + 1)))
+}
+
+/// Animates the folds, zooming in on the shrinking paper.
+fn render_animation_of_folds(
+    input_data: &PuzzleInput,
+    num_folds: usize,
+    context: &RenderContext,
+) -> Result<Vec<PathBuf>> {
+    let options = context.options().clone();
+    let canvas = options.frame_size.unwrap_or_else(|| {
+        // The unfolded paper, enlarged if it's tiny
+        let (width, height) = paper_size(&input_data.dots);
+        let scale = (800 / width.max(height)).max(1);
+        FrameSize {
+            width: (width * scale) as usize,
+            height: (height * scale) as usize,
+        }
+    });
+
+    let input_data = input_data.clone();
+    let num_frames = num_folds * (FOLD_FRAMES + 1) + 1;
+    render_animation(
+        context,
+        RenderFormat::Gif,
+        3.0,
+        Some(num_frames),
+        move |collector| generate_images(collector, &input_data, num_folds, canvas, &options),
+    )
+}
+
+/// Writes one SVG per fold step, starting with the unfolded paper.
+fn render_folds(
+    input_data: &PuzzleInput,
+    num_folds: usize,
+    context: &RenderContext,
+) -> Result<Vec<PathBuf>> {
+    let mut dots = input_data.dots.clone();
+    let mut size = paper_size(&dots);
+
+    let folds = &input_data.folds[..num_folds];
+    let mut artifacts = vec![];
+    for step in 0..=folds.len() {
+        let fold = folds.get(step);
+        let caption = match fold {
+            Some(fold) => fold_caption(fold),
+            None => format!("{} dots", dots.len()),
+        };
+
+        let output_path = context.artifact_path(Some(&format!("step{:02}", step)), "svg");
+        paper_to_svg(&dots, size, fold, &caption).save(&output_path)?;
+        artifacts.push(output_path);
+
+        if let Some(fold) = fold {
+            dots = fold_dots(dots, fold);
+            size = match fold {
+                Fold::X(seam) => (*seam, size.1),
+                Fold::Y(seam) => (size.0, *seam),
+            };
+        }
+    }
+
+    Ok(artifacts)
+}
+
+fn render(
+    input_data: &PuzzleInput,
+    num_folds: usize,
+    context: &RenderContext,
+) -> Result<Vec<PathBuf>> {
+    let num_folds = num_folds.min(input_data.folds.len());
+    let mut artifacts = render_animation_of_folds(input_data, num_folds, context)?;
+    artifacts.extend(render_folds(input_data, num_folds, context)?);
+    Ok(artifacts)
+}
+
+pub fn task1(input_data: &PuzzleInput, context: &RenderContext) -> Result<Vec<PathBuf>> {
+    render(input_data, 1, context)
+}
+
+pub fn task2(input_data: &PuzzleInput, context: &RenderContext) -> Result<Vec<PathBuf>> {
+    render(input_data, input_data.folds.len(), context)
+}
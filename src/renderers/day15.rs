@@ -1,8 +1,17 @@
+use std::path::PathBuf;
+
+use anyhow::Result;
+use imgref::ImgVec;
 use ndarray::Array2;
 use rgb::RGBA8;
 
 use crate::{
-    helpers::rendering::{Collector, FramesCounter, Renderers, ToColor, Writer},
+    helpers::rendering::{
+        colormap::{mix, Colormap, ValueRange},
+        overlay::{draw_caption, draw_legend},
+        render_animation, Collector, ErrorLatch, RenderContext, RenderFormat, RenderOptions,
+        ToColor,
+    },
     solutions::day15::get_wrapped_risk,
 };
 
@@ -13,30 +22,23 @@ struct MapTile {
     part_of_solution: bool,
 }
 
+const RISK_RANGE: ValueRange = ValueRange { min: 1.0, max: 9.0 };
+
 impl ToColor for MapTile {
     fn to_color(&self) -> RGBA8 {
-        let base_gray = 200 - self.risk * 12;
-        let mut r = base_gray;
-        let mut g = base_gray;
-        let mut b = base_gray;
+        // Light to medium gray, darker with higher risk
+        let risk = RISK_RANGE.normalize(self.risk as f64);
+        let base = Colormap::Grayscale.color(0.75 - 0.4 * risk);
 
         if self.part_of_solution {
-            r = 255;
-            g -= 60;
-            b -= 60;
-            g /= 2;
-            b /= 2;
+            mix(base, RGBA8::new(255, 0, 0, 255), 0.7)
         } else if self.solved.is_some() {
-            r -= 60;
-            g -= 60;
-            b += 55;
+            mix(base, RGBA8::new(0, 0, 255, 255), 0.4)
         } else if self.considered > 0 {
-            r -= 80;
-            g += 55;
-            b -= 80;
+            mix(base, RGBA8::new(0, 255, 0, 255), 0.4)
+        } else {
+            base
         }
-
-        RGBA8::new(r, g, b, 255)
     }
 }
 
@@ -48,9 +50,43 @@ struct RenderConfig<R> {
     astar: bool,
     speedup: usize,
     speedup_end: usize,
+    scale: usize,
+    options: RenderOptions,
+}
+
+fn to_frame<R>(
+    image_data: &Array2<MapTile>,
+    config: &RenderConfig<R>,
+    caption: &str,
+) -> Result<ImgVec<RGBA8>> {
+    let mut frame = config
+        .options
+        .frame_layout()
+        .map_to_image(image_data, config.scale)?;
+    if config.options.captions {
+        let tile = |considered, solved, part_of_solution| {
+            MapTile {
+                risk: 5,
+                considered,
+                solved,
+                part_of_solution,
+            }
+            .to_color()
+        };
+        draw_caption(&mut frame, caption);
+        draw_legend(
+            &mut frame,
+            &[
+                (tile(1, None, false), "CONSIDERED"),
+                (tile(1, Some((0, 0)), false), "SOLVED"),
+                (tile(1, Some((0, 0)), true), "PATH"),
+            ],
+        );
+    }
+    Ok(frame)
 }
 
-fn generate_images<R>(mut collector: impl Collector, config: &RenderConfig<R>) -> usize
+fn generate_images<R>(mut collector: impl Collector, config: &RenderConfig<R>) -> Result<()>
 where
     R: Fn((usize, usize)) -> Option<u8>,
 {
@@ -66,11 +102,15 @@ where
     image_data[config.start].part_of_solution = true;
     image_data[config.goal].part_of_solution = true;
 
-    collector.add_frame(&image_data, time_step * collector.get_num_frames() as f64);
+    collector.add_frame(
+        to_frame(&image_data, config, "COST 0")?,
+        time_step * collector.get_num_frames() as f64,
+    )?;
 
     // Run solving algorithm
     let mut skipped = 0;
-    crate::solutions::day15::find_shortest_path(
+    let mut latch = ErrorLatch::new();
+    let total_risk = crate::solutions::day15::find_shortest_path(
         config.start,
         config.goal,
         &config.map_read,
@@ -80,7 +120,12 @@ where
                 skipped += 1;
                 if skipped >= config.speedup {
                     skipped = 0;
-                    collector.add_frame(&image_data, time_step * collector.get_num_frames() as f64);
+                    latch.run(|| {
+                        collector.add_frame(
+                            to_frame(&image_data, config, &format!("COST {}", element.cost))?,
+                            time_step * collector.get_num_frames() as f64,
+                        )
+                    });
                 }
             } else {
                 image_data[element.coord].considered += 1;
@@ -89,8 +134,13 @@ where
         config.astar,
     )
     .unwrap();
+    latch.into_result()?;
+    let total_risk_caption = format!("TOTAL RISK {}", total_risk);
 
-    collector.add_frame(&image_data, time_step * collector.get_num_frames() as f64);
+    collector.add_frame(
+        to_frame(&image_data, config, &total_risk_caption)?,
+        time_step * collector.get_num_frames() as f64,
+    )?;
 
     // Animate path trace
     let mut current_path_coord = config.goal;
@@ -108,16 +158,44 @@ where
         skipped += 1;
         if skipped >= config.speedup_end {
             skipped = 0;
-            collector.add_frame(&image_data, time_step * collector.get_num_frames() as f64);
+            collector.add_frame(
+                to_frame(&image_data, config, &total_risk_caption)?,
+                time_step * collector.get_num_frames() as f64,
+            )?;
         }
     }
 
-    collector.add_frame(&image_data, time_step * collector.get_num_frames() as f64);
+    collector.add_frame(
+        to_frame(&image_data, config, &total_risk_caption)?,
+        time_step * collector.get_num_frames() as f64,
+    )?;
+
+    Ok(())
+}
 
-    collector.get_num_frames()
+/// An upper bound of the number of frames: every tile gets solved at most once,
+/// and the path can't be much longer than the way around the edges.
+fn estimate_num_frames<R>(config: &RenderConfig<R>) -> usize {
+    let num_tiles = config.map_size.0 * config.map_size.1;
+    let path_length = config.map_size.0 + config.map_size.1;
+    num_tiles / config.speedup + path_length / config.speedup_end + 3
 }
 
-pub fn task1(input_data: &Array2<u8>) -> Vec<String> {
+fn render<R>(config: RenderConfig<R>, context: &RenderContext) -> Result<Vec<PathBuf>>
+where
+    R: Fn((usize, usize)) -> Option<u8> + Send + 'static,
+{
+    let num_frames = estimate_num_frames(&config);
+    render_animation(
+        context,
+        RenderFormat::Webp,
+        4.0,
+        Some(num_frames),
+        move |collector| generate_images(collector, &config),
+    )
+}
+
+pub fn task1(input_data: &Array2<u8>, context: &RenderContext) -> Result<Vec<PathBuf>> {
     let map = input_data.clone();
     let map_read = move |coord| map.get(coord).cloned();
     let map_size = input_data.dim();
@@ -130,27 +208,14 @@ pub fn task1(input_data: &Array2<u8>) -> Vec<String> {
         astar: false,
         speedup: 15,
         speedup_end: 1,
+        scale: 3,
+        options: context.options().clone(),
     };
 
-    let num_frames = generate_images(FramesCounter::new(), &config);
-
-    let (collector, writer) = Renderers::create_webp_renderer(3, 4.0);
-
-    let collector_thread = std::thread::spawn(move || {
-        generate_images(collector, &config);
-    });
-
-    let filename = std::env::current_dir()
-        .unwrap()
-        .join("aoc2021_day15_task1.webp");
-    writer.write(&filename, Some(num_frames));
-
-    collector_thread.join().unwrap();
-
-    vec![filename.into_os_string().into_string().unwrap()]
+    render(config, context)
 }
 
-pub fn task2(input_data: &Array2<u8>) -> Vec<String> {
+pub fn task2(input_data: &Array2<u8>, context: &RenderContext) -> Result<Vec<PathBuf>> {
     let map = input_data.clone();
     let map_read = move |coord| get_wrapped_risk(&map, coord);
     let map_size = (input_data.dim().0 * 5, input_data.dim().1 * 5);
@@ -163,22 +228,9 @@ pub fn task2(input_data: &Array2<u8>) -> Vec<String> {
         astar: true,
         speedup: 500,
         speedup_end: 5,
+        scale: 1,
+        options: context.options().clone(),
     };
 
-    let num_frames = generate_images(FramesCounter::new(), &config);
-
-    let (collector, writer) = Renderers::create_webp_renderer(1, 4.0);
-
-    let collector_thread = std::thread::spawn(move || {
-        generate_images(collector, &config);
-    });
-
-    let filename = std::env::current_dir()
-        .unwrap()
-        .join("aoc2021_day15_task2.webp");
-    writer.write(&filename, Some(num_frames));
-
-    collector_thread.join().unwrap();
-
-    vec![filename.into_os_string().into_string().unwrap()]
+    render(config, context)
 }
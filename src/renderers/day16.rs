@@ -0,0 +1,133 @@
+use std::{fmt::Write, fs, path::PathBuf};
+
+use anyhow::{anyhow, Context, Result};
+use rgb::RGBA8;
+
+use crate::{
+    helpers::rendering::{
+        colormap::{mix, Colormap},
+        dot::{color, DotGraph},
+        RenderContext,
+    },
+    solutions::day16::{
+        get_accumulated_version_numbers, parse_packet, Instruction, Packet, Payload,
+    },
+};
+
+fn type_name(payload: &Payload) -> String {
+    match payload {
+        Payload::Literal(_) => "literal".to_string(),
+        Payload::Operator(instruction, _) => instruction.to_string(),
+    }
+}
+
+fn type_color(payload: &Payload) -> RGBA8 {
+    let id = match payload {
+        Payload::Literal(_) => 4,
+        Payload::Operator(instruction, _) => match instruction {
+            Instruction::Sum => 0,
+            Instruction::Product => 1,
+            Instruction::Minimum => 2,
+            Instruction::Maximum => 3,
+            Instruction::Greater => 5,
+            Instruction::Less => 6,
+            Instruction::Equal => 7,
+        },
+    };
+    // Lightened, to keep the labels readable
+    mix(
+        Colormap::categorical(id),
+        RGBA8::new(255, 255, 255, 255),
+        0.5,
+    )
+}
+
+/// Serializes the packet tree, with the evaluated result of every subtree.
+fn packet_to_json(packet: &Packet, indent: usize, json: &mut String) -> std::fmt::Result {
+    let padding = "  ".repeat(indent);
+    writeln!(json, "{{")?;
+    writeln!(json, "{}  \"version\": {},", padding, packet.version)?;
+    writeln!(
+        json,
+        "{}  \"type\": \"{}\",",
+        padding,
+        type_name(&packet.payload)
+    )?;
+    match &packet.payload {
+        Payload::Literal(value) => {
+            writeln!(json, "{}  \"value\": {}", padding, value)?;
+        }
+        Payload::Operator(_, children) => {
+            writeln!(json, "{}  \"value\": {},", padding, packet.evaluate())?;
+            write!(json, "{}  \"children\": [", padding)?;
+            for (id, child) in children.iter().enumerate() {
+                write!(json, "{}\n{}    ", if id > 0 { "," } else { "" }, padding)?;
+                packet_to_json(child, indent + 2, json)?;
+            }
+            write!(json, "\n{}  ]\n", padding)?;
+        }
+    }
+    write!(json, "{}}}", padding)
+}
+
+fn add_packet_to_dot(packet: &Packet, dot: &mut DotGraph, next_id: &mut usize) -> String {
+    let id = format!("p{}", next_id);
+    *next_id += 1;
+
+    let label = match &packet.payload {
+        Payload::Literal(value) => format!("{}\nv{}", value, packet.version),
+        Payload::Operator(instruction, _) => format!(
+            "{}\nv{}\n= {}",
+            instruction,
+            packet.version,
+            packet.evaluate()
+        ),
+    };
+    let shape = match packet.payload {
+        Payload::Literal(_) => "ellipse",
+        Payload::Operator(..) => "box",
+    };
+    let fill = color(type_color(&packet.payload));
+    dot.node(
+        &id,
+        &[("label", &label), ("shape", shape), ("fillcolor", &fill)],
+    );
+
+    if let Payload::Operator(_, children) = &packet.payload {
+        for child in children {
+            let child_id = add_packet_to_dot(child, dot, next_id);
+            dot.edge(&id, &child_id, &[]);
+        }
+    }
+    id
+}
+
+fn export(packet: &Packet, title: &str, context: &RenderContext) -> Result<Vec<PathBuf>> {
+    let mut dot = DotGraph::new("packets", true);
+    dot.defaults("graph", &[("label", title), ("labelloc", "t")]);
+    dot.defaults("node", &[("style", "filled"), ("fontname", "monospace")]);
+    add_packet_to_dot(packet, &mut dot, &mut 0);
+    let dot_path = context.output_path(None, "dot")?;
+    dot.save(&dot_path)?;
+
+    let mut json = String::new();
+    packet_to_json(packet, 0, &mut json)?;
+    json.push('\n');
+    let json_path = context.artifact_path(None, "json");
+    fs::write(&json_path, json)
+        .with_context(|| format!("Unable to write '{}'", json_path.display()))?;
+
+    Ok(vec![dot_path, json_path])
+}
+
+pub fn task1(input_data: &str, context: &RenderContext) -> Result<Vec<PathBuf>> {
+    let packet = parse_packet(input_data).ok_or_else(|| anyhow!("Incomplete packet"))?;
+    let title = format!("version sum {}", get_accumulated_version_numbers(&packet));
+    export(&packet, &title, context)
+}
+
+pub fn task2(input_data: &str, context: &RenderContext) -> Result<Vec<PathBuf>> {
+    let packet = parse_packet(input_data).ok_or_else(|| anyhow!("Incomplete packet"))?;
+    let title = format!("result {}", packet.evaluate());
+    export(&packet, &title, context)
+}
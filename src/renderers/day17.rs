@@ -0,0 +1,256 @@
+use std::path::PathBuf;
+
+use anyhow::{anyhow, Result};
+use imgref::ImgVec;
+use ndarray::Array2;
+use rgb::RGBA8;
+
+use crate::{
+    helpers::rendering::{
+        colormap::{mix, Colormap, ValueRange},
+        overlay::{draw_caption, draw_legend},
+        render_animation, write_png, Collector, RenderContext, RenderFormat, RenderOptions,
+        ToColor,
+    },
+    solutions::day17::{drag, reaches, Rect},
+};
+
+const TARGET_COLOR: RGBA8 = RGBA8::new(0, 200, 0, 255);
+const TRAJECTORY_COLOR: RGBA8 = RGBA8::new(255, 255, 255, 255);
+const PROBE_COLOR: RGBA8 = RGBA8::new(255, 0, 0, 255);
+
+#[derive(Clone, Copy)]
+struct Cell {
+    /// How often valid trajectories pass this cell, normalized to `0..=1`
+    heat: Option<f64>,
+    target: bool,
+    trajectory: bool,
+    probe: bool,
+}
+
+impl ToColor for Cell {
+    fn to_color(&self) -> RGBA8 {
+        let base = match self.heat {
+            Some(heat) => Colormap::Magma.color(0.3 + 0.7 * heat),
+            None => Colormap::Magma.color(0.05),
+        };
+        if self.probe {
+            PROBE_COLOR
+        } else if self.trajectory {
+            TRAJECTORY_COLOR
+        } else if self.target {
+            mix(base, TARGET_COLOR, 0.6)
+        } else {
+            base
+        }
+    }
+}
+
+/// Maps probe positions to cells of the plot. The sky is much higher than the
+/// target is wide, so every cell covers multiple units of height.
+#[derive(Clone)]
+struct Plot {
+    x_min: i32,
+    y_max: i32,
+    y_step: i32,
+    cells: Array2<Cell>,
+}
+
+impl Plot {
+    fn new(target: &Rect, y_max: i32) -> Self {
+        let x_min = i32::min(0, *target.x.start());
+        let x_max = i32::max(0, *target.x.end());
+        let y_min = i32::min(0, *target.y.start());
+
+        let width = x_max - x_min + 1;
+        let height = y_max - y_min + 1;
+        let y_step = (height + width - 1) / width;
+
+        let mut plot = Self {
+            x_min,
+            y_max,
+            y_step,
+            cells: Array2::from_elem(
+                (((height + y_step - 1) / y_step) as usize, width as usize),
+                Cell {
+                    heat: None,
+                    target: false,
+                    trajectory: false,
+                    probe: false,
+                },
+            ),
+        };
+        for x in target.x.clone() {
+            for y in target.y.clone() {
+                if let Some(cell) = plot.get_mut((x, y)) {
+                    cell.target = true;
+                }
+            }
+        }
+        plot
+    }
+
+    fn index(&self, (x, y): (i32, i32)) -> Option<(usize, usize)> {
+        let (rows, columns) = self.cells.dim();
+        let row = (self.y_max - y) / self.y_step;
+        let column = x - self.x_min;
+        (row >= 0 && (row as usize) < rows && column >= 0 && (column as usize) < columns)
+            .then_some((row as usize, column as usize))
+    }
+
+    fn get_mut(&mut self, position: (i32, i32)) -> Option<&mut Cell> {
+        let index = self.index(position)?;
+        self.cells.get_mut(index)
+    }
+
+    fn add_heatmap(&mut self, trajectories: &[Vec<(i32, i32)>]) {
+        let mut visits = Array2::<u32>::zeros(self.cells.dim());
+        for trajectory in trajectories {
+            for &position in trajectory {
+                if let Some(index) = self.index(position) {
+                    visits[index] += 1;
+                }
+            }
+        }
+
+        // Few cells get passed by most trajectories, so compress the range
+        let range = ValueRange::of(visits.iter().map(|&count| (count as f64).ln_1p()));
+        for (cell, &count) in self.cells.iter_mut().zip(visits.iter()) {
+            if count > 0 {
+                cell.heat = Some(range.normalize((count as f64).ln_1p()));
+            }
+        }
+    }
+}
+
+/// All positions of the probe, until it hits the target or falls below it.
+fn trajectory(velocity: (i32, i32), target: &Rect) -> Vec<(i32, i32)> {
+    let (mut x, mut y) = (0, 0);
+    let (mut vx, mut vy) = velocity;
+    let mut positions = vec![(x, y)];
+    loop {
+        let hit = target.x.contains(&x) && target.y.contains(&y);
+        let missed = y < *target.y.start() && vy < 0;
+        if hit || missed {
+            break;
+        }
+        x += vx;
+        y += vy;
+        vx = drag(vx);
+        vy -= 1;
+        positions.push((x, y));
+    }
+    positions
+}
+
+/// All launch velocities that hit the target, within the same bounds as the solver.
+fn valid_velocities(target: &Rect) -> Vec<(i32, i32)> {
+    (*target.y.start()..=-target.y.start())
+        .flat_map(|vy| (0..=*target.x.end()).map(move |vx| (vx, vy)))
+        .filter(|&velocity| reaches((0, 0), velocity, target))
+        .collect()
+}
+
+fn apex(trajectory: &[(i32, i32)]) -> i32 {
+    trajectory.iter().map(|&(_, y)| y).max().unwrap_or(0)
+}
+
+fn to_frame(
+    plot: &Plot,
+    options: &RenderOptions,
+    caption: impl FnOnce() -> String,
+) -> Result<ImgVec<RGBA8>> {
+    let (rows, columns) = plot.cells.dim();
+    let scale = (600 / rows.max(columns)).max(1);
+    let mut frame = options.frame_layout().map_to_image(&plot.cells, scale)?;
+    if options.captions {
+        let caption = caption();
+        draw_caption(&mut frame, &caption);
+        draw_legend(
+            &mut frame,
+            &[
+                (TARGET_COLOR, "TARGET"),
+                (TRAJECTORY_COLOR, "TRAJECTORY"),
+                (PROBE_COLOR, "PROBE"),
+            ],
+        );
+    }
+    Ok(frame)
+}
+
+fn generate_images(
+    mut collector: impl Collector,
+    mut plot: Plot,
+    best: &[(i32, i32)],
+    options: &RenderOptions,
+) -> Result<()> {
+    let time_step = 1.0 / 30.0;
+
+    for (step, &position) in best.iter().enumerate() {
+        if let Some(cell) = plot.get_mut(position) {
+            cell.probe = true;
+        }
+        collector.add_frame(
+            to_frame(&plot, options, || {
+                format!("STEP {}\nHEIGHT {}", step, position.1)
+            })?,
+            time_step * collector.get_num_frames() as f64,
+        )?;
+        if let Some(cell) = plot.get_mut(position) {
+            cell.probe = false;
+            cell.trajectory = true;
+        }
+    }
+
+    Ok(())
+}
+
+fn render(target: &Rect, heatmap: bool, context: &RenderContext) -> Result<Vec<PathBuf>> {
+    let trajectories = valid_velocities(target)
+        .into_iter()
+        .map(|velocity| trajectory(velocity, target))
+        .collect::<Vec<_>>();
+    let best = trajectories
+        .iter()
+        .max_by_key(|trajectory| apex(trajectory))
+        .ok_or_else(|| anyhow!("No launch velocity hits the target"))?
+        .clone();
+
+    let mut plot = Plot::new(target, apex(&best));
+    if heatmap {
+        plot.add_heatmap(&trajectories);
+    }
+
+    // The still image shows the full flight at once
+    let mut still = plot.clone();
+    for &position in &best {
+        if let Some(cell) = still.get_mut(position) {
+            cell.trajectory = true;
+        }
+    }
+    let image = to_frame(&still, context.options(), || {
+        format!("APEX {}\nVELOCITIES {}", apex(&best), trajectories.len())
+    })?;
+    let image_path = context.artifact_path(Some("still"), "png");
+    write_png(&image_path, &image)?;
+
+    let num_frames = best.len();
+    let options = context.options().clone();
+    let mut artifacts = render_animation(
+        context,
+        RenderFormat::Gif,
+        3.0,
+        Some(num_frames),
+        move |collector| generate_images(collector, plot, &best, &options),
+    )?;
+    artifacts.push(image_path);
+    Ok(artifacts)
+}
+
+pub fn task1(input_data: &Rect, context: &RenderContext) -> Result<Vec<PathBuf>> {
+    render(input_data, false, context)
+}
+
+pub fn task2(input_data: &Rect, context: &RenderContext) -> Result<Vec<PathBuf>> {
+    render(input_data, true, context)
+}
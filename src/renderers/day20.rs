@@ -0,0 +1,146 @@
+use std::path::PathBuf;
+
+use anyhow::Result;
+use imgref::ImgVec;
+use ndarray::{s, Array2};
+use rgb::RGBA8;
+
+use crate::{
+    helpers::rendering::{
+        overlay::{draw_caption, draw_legend},
+        render_animation, Collector, RenderContext, RenderFormat, RenderOptions, ToColor,
+    },
+    solutions::day20::{enhance, PuzzleInput, BORDER_SIZE},
+};
+
+#[derive(Clone, Copy)]
+struct Pixel {
+    lit: bool,
+    /// Outside of the area the input image can have influenced yet,
+    /// so part of the infinite background.
+    background: bool,
+}
+
+impl ToColor for Pixel {
+    fn to_color(&self) -> RGBA8 {
+        match (self.background, self.lit) {
+            (false, true) => RGBA8::new(255, 255, 255, 255),
+            (false, false) => RGBA8::new(0, 0, 0, 255),
+            (true, true) => RGBA8::new(150, 150, 200, 255),
+            (true, false) => RGBA8::new(30, 30, 60, 255),
+        }
+    }
+}
+
+/// Crops the padding, keeping the area the image grows into until the last round.
+fn to_frame(
+    image: &Array2<char>,
+    round: usize,
+    rounds: usize,
+    options: &RenderOptions,
+) -> Result<ImgVec<RGBA8>> {
+    // Every round removes one pixel of padding on each side
+    let offset = BORDER_SIZE - round;
+    let height = image.nrows() - 2 * offset;
+    let width = image.ncols() - 2 * offset;
+    let crop = image.slice(s![
+        offset - rounds..offset + height + rounds,
+        offset - rounds..offset + width + rounds
+    ]);
+
+    // The input image grows by one pixel on each side per round
+    let margin = rounds - round;
+    let pixels = Array2::from_shape_fn(crop.dim(), |(y, x)| Pixel {
+        lit: crop[(y, x)] == '#',
+        background: y < margin
+            || x < margin
+            || y >= margin + height + 2 * round
+            || x >= margin + width + 2 * round,
+    });
+
+    let scale = (600 / pixels.nrows().max(pixels.ncols())).max(1);
+    let mut frame = options.frame_layout().map_to_image(&pixels, scale)?;
+    if options.captions {
+        let lit = if image[(0, 0)] == '#' {
+            "INFINITE".to_string()
+        } else {
+            image
+                .iter()
+                .filter(|&&pixel| pixel == '#')
+                .count()
+                .to_string()
+        };
+        draw_caption(&mut frame, &format!("ROUND {}\nLIT {}", round, lit));
+        draw_legend(
+            &mut frame,
+            &[
+                (
+                    Pixel {
+                        lit: true,
+                        background: false,
+                    }
+                    .to_color(),
+                    "LIT",
+                ),
+                (
+                    Pixel {
+                        lit: true,
+                        background: true,
+                    }
+                    .to_color(),
+                    "LIT BACKGROUND",
+                ),
+            ],
+        );
+    }
+    Ok(frame)
+}
+
+fn generate_images(
+    mut collector: impl Collector,
+    input_data: &PuzzleInput,
+    rounds: usize,
+    options: &RenderOptions,
+) -> Result<()> {
+    let time_step = 0.2;
+
+    let mut image = input_data.image.clone();
+    collector.add_frame(
+        to_frame(&image, 0, rounds, options)?,
+        time_step * collector.get_num_frames() as f64,
+    )?;
+
+    for round in 1..=rounds {
+        image = enhance(&image, &input_data.enhancement_lookup);
+        collector.add_frame(
+            to_frame(&image, round, rounds, options)?,
+            time_step * collector.get_num_frames() as f64,
+        )?;
+    }
+
+    Ok(())
+}
+
+fn render(
+    input_data: &PuzzleInput,
+    rounds: usize,
+    context: &RenderContext,
+) -> Result<Vec<PathBuf>> {
+    let input_data = input_data.clone();
+    let options = context.options().clone();
+    render_animation(
+        context,
+        RenderFormat::Gif,
+        3.0,
+        Some(rounds + 1),
+        move |collector| generate_images(collector, &input_data, rounds, &options),
+    )
+}
+
+pub fn task1(input_data: &PuzzleInput, context: &RenderContext) -> Result<Vec<PathBuf>> {
+    render(input_data, 2, context)
+}
+
+pub fn task2(input_data: &PuzzleInput, context: &RenderContext) -> Result<Vec<PathBuf>> {
+    render(input_data, 50, context)
+}
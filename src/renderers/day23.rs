@@ -0,0 +1,239 @@
+use std::{collections::HashMap, path::PathBuf};
+
+use anyhow::{anyhow, Result};
+use imgref::ImgVec;
+use ndarray::Array2;
+use rgb::RGBA8;
+
+use crate::{
+    helpers::rendering::{
+        colormap::Colormap,
+        overlay::{draw_caption, draw_legend},
+        render_animation, Collector, RenderContext, RenderFormat, RenderOptions, ToColor,
+    },
+    solutions::day23::{
+        find_cheapest_solution, solution_path, unfold_burrow, Amphipod, GameState, HallwayTile,
+    },
+};
+
+#[derive(Clone, Copy)]
+enum BurrowTile {
+    Rock,
+    Wall,
+    Floor,
+    Amphipod(Amphipod),
+}
+
+impl ToColor for BurrowTile {
+    fn to_color(&self) -> RGBA8 {
+        match self {
+            BurrowTile::Rock => RGBA8::new(20, 20, 20, 255),
+            BurrowTile::Wall => RGBA8::new(90, 90, 90, 255),
+            BurrowTile::Floor => RGBA8::new(220, 220, 220, 255),
+            BurrowTile::Amphipod(amphi) => Colormap::categorical(amphi.home_chamber() as i64),
+        }
+    }
+}
+
+/// A single amphipod moving from one cell of the burrow to another,
+/// both in (row, column) of the burrow map.
+struct Move {
+    amphipod: Amphipod,
+    from: (usize, usize),
+    to: (usize, usize),
+}
+
+impl Move {
+    /// The cells on the way, without the start cell: out of the chamber,
+    /// along the hallway, and into the next chamber.
+    fn path(&self) -> Vec<(usize, usize)> {
+        let hallway_row = 1;
+        let mut path = vec![];
+        path.extend(
+            (hallway_row..self.from.0)
+                .rev()
+                .map(|row| (row, self.from.1)),
+        );
+        if self.from.1 < self.to.1 {
+            path.extend((self.from.1 + 1..=self.to.1).map(|column| (hallway_row, column)));
+        } else {
+            path.extend(
+                (self.to.1..self.from.1)
+                    .rev()
+                    .map(|column| (hallway_row, column)),
+            );
+        }
+        path.extend((hallway_row + 1..=self.to.0).map(|row| (row, self.to.1)));
+        path
+    }
+}
+
+/// The positions of all amphipods, in (row, column) of the burrow map.
+fn amphipod_positions(state: &GameState) -> HashMap<(usize, usize), Amphipod> {
+    let hallway = state
+        .hallway
+        .iter()
+        .enumerate()
+        .filter_map(|(position, tile)| match tile {
+            HallwayTile::Occupiable(Some(amphi)) => Some(((1, position + 1), *amphi)),
+            _ => None,
+        });
+    let chambers = state
+        .chambers
+        .iter()
+        .enumerate()
+        .flat_map(|(chamber_id, chamber)| {
+            let column = GameState::chamber_id_to_position(chamber_id) + 1;
+            chamber
+                .content
+                .iter()
+                .enumerate()
+                .filter_map(move |(depth, amphi)| amphi.map(|a| ((depth + 2, column), a)))
+        });
+    hallway.chain(chambers).collect()
+}
+
+fn find_move(previous: &GameState, next: &GameState) -> Result<Move> {
+    let previous = amphipod_positions(previous);
+    let next = amphipod_positions(next);
+    let from = previous
+        .keys()
+        .find(|position| !next.contains_key(position));
+    let to = next
+        .keys()
+        .find(|position| !previous.contains_key(position));
+    match (from, to) {
+        (Some(&from), Some(&to)) => Ok(Move {
+            amphipod: next[&to],
+            from,
+            to,
+        }),
+        _ => Err(anyhow!("Consecutive states don't differ by a single move")),
+    }
+}
+
+/// The empty burrow, drawn like the puzzle input.
+fn empty_burrow(chamber_depth: usize) -> Array2<BurrowTile> {
+    let mut lines = vec!["#############", "#...........#", "###.#.#.#.###"];
+    lines.extend(std::iter::repeat_n("  #.#.#.#.#  ", chamber_depth - 1));
+    lines.push("  #########  ");
+
+    Array2::from_shape_fn((lines.len(), 13), |(row, column)| {
+        match lines[row].as_bytes()[column] {
+            b'#' => BurrowTile::Wall,
+            b'.' => BurrowTile::Floor,
+            _ => BurrowTile::Rock,
+        }
+    })
+}
+
+/// Splits every cell into multiple tiles, to leave a gap of floor around the amphipods.
+fn to_tiles(burrow: &Array2<BurrowTile>) -> Array2<BurrowTile> {
+    let cell_size = 8;
+    let (rows, columns) = burrow.dim();
+    Array2::from_shape_fn((rows * cell_size, columns * cell_size), |(y, x)| {
+        let tile = burrow[(y / cell_size, x / cell_size)];
+        let is_border = [y % cell_size, x % cell_size]
+            .iter()
+            .any(|&offset| offset == 0 || offset == cell_size - 1);
+        match tile {
+            BurrowTile::Amphipod(_) if is_border => BurrowTile::Floor,
+            tile => tile,
+        }
+    })
+}
+
+fn to_frame(
+    burrow: &Array2<BurrowTile>,
+    options: &RenderOptions,
+    caption: impl FnOnce() -> String,
+) -> Result<ImgVec<RGBA8>> {
+    let scale = 5;
+    let mut frame = options
+        .frame_layout()
+        .map_to_image(&to_tiles(burrow), scale)?;
+    if options.captions {
+        let caption = caption();
+        draw_caption(&mut frame, &caption);
+        draw_legend(
+            &mut frame,
+            &[
+                (BurrowTile::Amphipod(Amphipod::A).to_color(), "A"),
+                (BurrowTile::Amphipod(Amphipod::B).to_color(), "B"),
+                (BurrowTile::Amphipod(Amphipod::C).to_color(), "C"),
+                (BurrowTile::Amphipod(Amphipod::D).to_color(), "D"),
+            ],
+        );
+    }
+    Ok(frame)
+}
+
+fn generate_images(
+    mut collector: impl Collector,
+    initial_state: &GameState,
+    moves: &[Move],
+    options: &RenderOptions,
+) -> Result<()> {
+    let time_step = 1.0 / 15.0;
+    let pause = 0.3;
+    let mut time = 0.0;
+
+    let chamber_depth = initial_state.chambers[0].content.len();
+    let mut burrow = empty_burrow(chamber_depth);
+    for (position, amphi) in amphipod_positions(initial_state) {
+        burrow[position] = BurrowTile::Amphipod(amphi);
+    }
+
+    let mut energy = 0;
+    let caption =
+        |move_id, energy| move || format!("MOVE {}/{}\nENERGY {}", move_id, moves.len(), energy);
+    collector.add_frame(to_frame(&burrow, options, caption(0, energy))?, time)?;
+    time += pause;
+
+    for (move_id, amphi_move) in moves.iter().enumerate() {
+        let mut position = amphi_move.from;
+        for next_position in amphi_move.path() {
+            burrow[position] = BurrowTile::Floor;
+            burrow[next_position] = BurrowTile::Amphipod(amphi_move.amphipod);
+            position = next_position;
+            energy += amphi_move.amphipod.move_cost();
+
+            collector.add_frame(
+                to_frame(&burrow, options, caption(move_id + 1, energy))?,
+                time,
+            )?;
+            time += time_step;
+        }
+        time += pause;
+    }
+
+    Ok(())
+}
+
+fn render(initial_state: GameState, context: &RenderContext) -> Result<Vec<PathBuf>> {
+    let (solution, solution_map) =
+        find_cheapest_solution(&initial_state).ok_or_else(|| anyhow!("No solution found"))?;
+    let moves = solution_path(&solution, &solution_map)
+        .windows(2)
+        .map(|states| find_move(&states[0], &states[1]))
+        .collect::<Result<Vec<_>>>()?;
+
+    let num_frames = 1 + moves.iter().map(|m| m.path().len()).sum::<usize>();
+
+    let options = context.options().clone();
+    render_animation(
+        context,
+        RenderFormat::Gif,
+        3.0,
+        Some(num_frames),
+        move |collector| generate_images(collector, &initial_state, &moves, &options),
+    )
+}
+
+pub fn task1(input_data: &GameState, context: &RenderContext) -> Result<Vec<PathBuf>> {
+    render(input_data.clone(), context)
+}
+
+pub fn task2(input_data: &GameState, context: &RenderContext) -> Result<Vec<PathBuf>> {
+    render(unfold_burrow(input_data), context)
+}
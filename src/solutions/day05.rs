@@ -8,14 +8,14 @@ use crate::helpers::input_parsing::ParseError;
 
 #[derive(Debug, Clone)]
 pub struct Coord {
-    x: usize,
-    y: usize,
+    pub x: usize,
+    pub y: usize,
 }
 
 #[derive(Debug)]
 pub struct VentLine {
-    start: Coord,
-    end: Coord,
+    pub start: Coord,
+    pub end: Coord,
 }
 
 impl FromStr for VentLine {
@@ -70,7 +70,15 @@ pub fn is_minimum((height, width): (usize, usize), map: &Array2<i64>) -> bool {
     }
 }
 
-fn flood_fill(map: &Array2<i64>, basin: &mut HashSet<(usize, usize)>, index: (usize, usize)) {
+/// Fills the basin around `index`, calling `on_visit` for every field that gets added to it.
+pub fn flood_fill<F>(
+    map: &Array2<i64>,
+    basin: &mut HashSet<(usize, usize)>,
+    index: (usize, usize),
+    on_visit: &mut F,
+) where
+    F: FnMut((usize, usize)),
+{
     if let Some(&field) = map.get(index) {
         if field == 9 {
             return;
@@ -79,15 +87,16 @@ fn flood_fill(map: &Array2<i64>, basin: &mut HashSet<(usize, usize)>, index: (us
         if !basin.insert(index) {
             return;
         }
+        on_visit(index);
 
-        flood_fill(map, basin, (index.0 + 1, index.1));
-        flood_fill(map, basin, (index.0, index.1 + 1));
+        flood_fill(map, basin, (index.0 + 1, index.1), on_visit);
+        flood_fill(map, basin, (index.0, index.1 + 1), on_visit);
 
         if index.0 > 0 {
-            flood_fill(map, basin, (index.0 - 1, index.1));
+            flood_fill(map, basin, (index.0 - 1, index.1), on_visit);
         }
         if index.1 > 0 {
-            flood_fill(map, basin, (index.0, index.1 - 1));
+            flood_fill(map, basin, (index.0, index.1 - 1), on_visit);
         }
     }
 }
@@ -103,7 +112,7 @@ pub fn task2(input_data: &str) -> usize {
             // Perform flood fill
             let mut basin = HashSet::new();
 
-            flood_fill(&map, &mut basin, index);
+            flood_fill(&map, &mut basin, index, &mut |_| {});
 
             //println!("{:?} => {}: {:?}", index, basin.len(), basin);
 
@@ -4,9 +4,9 @@ use itertools::Itertools;
 
 #[derive(Debug)]
 pub struct GraphNode {
-    name: String,
-    is_large: bool,
-    neighbors: Vec<usize>,
+    pub name: String,
+    pub is_large: bool,
+    pub neighbors: Vec<usize>,
 }
 
 pub fn parse_input(input_data: &str) -> Vec<GraphNode> {
@@ -51,15 +51,25 @@ pub fn parse_input(input_data: &str) -> Vec<GraphNode> {
         .collect()
 }
 
-fn find_num_paths(
+/// Counts the paths from `node_id` to `end`.
+/// `on_path` receives the caves of every path found, `path` holds the caves leading up to `node_id`.
+pub fn find_num_paths<F>(
     graph: &[GraphNode],
     node_id: usize,
     end: usize,
     visited: &mut HashSet<usize>,
+    path: &mut Vec<usize>,
     depth: usize,
-) -> usize {
+    on_path: &mut F,
+) -> usize
+where
+    F: FnMut(&[usize]),
+{
     if node_id == end {
         //println!("{} -> end #PATH", "  ".repeat(depth));
+        path.push(node_id);
+        on_path(path);
+        path.pop();
         return 1;
     }
 
@@ -70,11 +80,13 @@ fn find_num_paths(
         return 0;
     }
 
+    path.push(node_id);
     let paths = node
         .neighbors
         .iter()
-        .map(|&neighbor| find_num_paths(graph, neighbor, end, visited, depth + 1))
+        .map(|&neighbor| find_num_paths(graph, neighbor, end, visited, path, depth + 1, on_path))
         .sum();
+    path.pop();
 
     if !node.is_large {
         visited.remove(&node_id);
@@ -83,34 +95,63 @@ fn find_num_paths(
     paths
 }
 
+/// Counts for every tunnel how many paths use it, each path at most once per tunnel.
+/// Tunnels can be used in both directions, so they are keyed by their unordered pair of caves.
+pub fn tunnel_counter(
+    tunnel_counts: &mut HashMap<(usize, usize), usize>,
+) -> impl FnMut(&[usize]) + '_ {
+    move |path| {
+        let tunnels = path
+            .iter()
+            .tuple_windows()
+            .map(|(&from, &to)| (from.min(to), from.max(to)))
+            .collect::<HashSet<_>>();
+        for tunnel in tunnels {
+            *tunnel_counts.entry(tunnel).or_default() += 1;
+        }
+    }
+}
+
+pub fn find_node(graph: &[GraphNode], name: &str) -> Option<usize> {
+    graph.iter().position(|node| node.name == name)
+}
+
 pub fn task1(graph: &[GraphNode]) -> usize {
-    let start_node = graph
-        .iter()
-        .enumerate()
-        .find(|(_, node)| node.name == "start")
-        .unwrap()
-        .0;
-    let end_node = graph
-        .iter()
-        .enumerate()
-        .find(|(_, node)| node.name == "end")
-        .unwrap()
-        .0;
+    let start_node = find_node(graph, "start").unwrap();
+    let end_node = find_node(graph, "end").unwrap();
 
-    find_num_paths(graph, start_node, end_node, &mut HashSet::new(), 0)
+    find_num_paths(
+        graph,
+        start_node,
+        end_node,
+        &mut HashSet::new(),
+        &mut Vec::new(),
+        0,
+        &mut |_| {},
+    )
 }
 
-fn find_num_paths_double_small(
+/// Like `find_num_paths`, but a single small cave may be visited twice.
+#[allow(clippy::too_many_arguments)]
+pub fn find_num_paths_double_small<F>(
     graph: &[GraphNode],
     node_id: usize,
     start: usize,
     end: usize,
     visited: &mut HashSet<usize>,
+    path: &mut Vec<usize>,
     mut small_twice: bool,
     depth: usize,
-) -> usize {
+    on_path: &mut F,
+) -> usize
+where
+    F: FnMut(&[usize]),
+{
     if node_id == end {
         //println!("{} -> end #PATH", "  ".repeat(depth));
+        path.push(node_id);
+        on_path(path);
+        path.pop();
         return 1;
     }
 
@@ -130,6 +171,7 @@ fn find_num_paths_double_small(
         }
     };
 
+    path.push(node_id);
     let paths = node
         .neighbors
         .iter()
@@ -140,11 +182,14 @@ fn find_num_paths_double_small(
                 start,
                 end,
                 visited,
+                path,
                 small_twice,
                 depth + 1,
+                on_path,
             )
         })
         .sum();
+    path.pop();
 
     if inserted {
         visited.remove(&node_id);
@@ -154,18 +199,8 @@ fn find_num_paths_double_small(
 }
 
 pub fn task2(graph: &[GraphNode]) -> usize {
-    let start_node = graph
-        .iter()
-        .enumerate()
-        .find(|(_, node)| node.name == "start")
-        .unwrap()
-        .0;
-    let end_node = graph
-        .iter()
-        .enumerate()
-        .find(|(_, node)| node.name == "end")
-        .unwrap()
-        .0;
+    let start_node = find_node(graph, "start").unwrap();
+    let end_node = find_node(graph, "end").unwrap();
 
     find_num_paths_double_small(
         graph,
@@ -173,8 +208,10 @@ pub fn task2(graph: &[GraphNode]) -> usize {
         start_node,
         end_node,
         &mut HashSet::new(),
+        &mut Vec::new(),
         false,
         0,
+        &mut |_| {},
     )
 }
 
@@ -192,3 +229,78 @@ crate::aoc_tests! {
         complex => 152837,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tunnel_counts(graph: &[GraphNode], double_small: bool) -> HashMap<(&str, &str), usize> {
+        let start = find_node(graph, "start").unwrap();
+        let end = find_node(graph, "end").unwrap();
+
+        let mut counts = HashMap::new();
+        {
+            let mut on_path = tunnel_counter(&mut counts);
+            if double_small {
+                find_num_paths_double_small(
+                    graph,
+                    start,
+                    start,
+                    end,
+                    &mut HashSet::new(),
+                    &mut Vec::new(),
+                    false,
+                    0,
+                    &mut on_path,
+                );
+            } else {
+                find_num_paths(
+                    graph,
+                    start,
+                    end,
+                    &mut HashSet::new(),
+                    &mut Vec::new(),
+                    0,
+                    &mut on_path,
+                );
+            }
+        }
+
+        counts
+            .into_iter()
+            .map(|((a, b), count)| {
+                let (a, b) = (graph[a].name.as_str(), graph[b].name.as_str());
+                ((a.min(b), a.max(b)), count)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn counts_tunnels_once_per_path() {
+        let graph = parse_input("start-A\nstart-b\nA-c\nA-b\nb-d\nA-end\nb-end\n");
+
+        assert_eq!(
+            tunnel_counts(&graph, false),
+            HashMap::from([
+                (("A", "b"), 7),
+                (("A", "c"), 5),
+                (("A", "end"), 7),
+                (("A", "start"), 7),
+                (("b", "end"), 3),
+                (("b", "start"), 3),
+            ])
+        );
+        assert_eq!(
+            tunnel_counts(&graph, true),
+            HashMap::from([
+                (("A", "b"), 31),
+                (("A", "c"), 23),
+                (("A", "end"), 24),
+                (("A", "start"), 24),
+                (("b", "d"), 8),
+                (("b", "end"), 12),
+                (("b", "start"), 12),
+            ])
+        );
+    }
+}
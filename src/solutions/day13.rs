@@ -6,8 +6,8 @@ use std::{
 
 #[derive(Debug, Hash, PartialEq, Eq, Clone)]
 pub struct Dot {
-    x: i64,
-    y: i64,
+    pub x: i64,
+    pub y: i64,
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -16,10 +16,10 @@ pub enum Fold {
     Y(i64),
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct PuzzleInput {
-    dots: HashSet<Dot>,
-    folds: Vec<Fold>,
+    pub dots: HashSet<Dot>,
+    pub folds: Vec<Fold>,
 }
 
 pub fn parse_input(input_data: &str) -> PuzzleInput {
@@ -26,8 +26,8 @@ impl<const N: usize> std::fmt::Display for Int<N> {
 
 #[derive(Debug)]
 pub struct Packet {
-    version: Int<3>,
-    payload: Payload,
+    pub version: Int<3>,
+    pub payload: Payload,
 }
 
 #[derive(Debug)]
@@ -204,7 +204,11 @@ fn packet_tree_to_string(packet: &Packet) -> Result<String, Box<dyn std::error::
     Ok(result)
 }
 
-fn get_accumulated_version_numbers(packet: &Packet) -> usize {
+pub fn parse_packet(input_data: &str) -> Option<Packet> {
+    parse::packet(&mut hex_to_binary_stream(input_data))
+}
+
+pub fn get_accumulated_version_numbers(packet: &Packet) -> usize {
     packet.version.to_num::<usize>()
         + match &packet.payload {
             Payload::Literal(_) => 0,
@@ -215,15 +219,13 @@ fn get_accumulated_version_numbers(packet: &Packet) -> usize {
 }
 
 pub fn task1(input_data: &str) -> usize {
-    let stream = &mut hex_to_binary_stream(input_data);
-    let packet = parse::packet(stream).unwrap();
+    let packet = parse_packet(input_data).unwrap();
 
     get_accumulated_version_numbers(&packet)
 }
 
 pub fn task2(input_data: &str) -> u64 {
-    let stream = &mut hex_to_binary_stream(input_data);
-    let packet = parse::packet(stream).unwrap();
+    let packet = parse_packet(input_data).unwrap();
 
     //println!("{}", packet_tree_to_string(&packet).unwrap());
 
@@ -29,8 +29,8 @@ mod parser {
 
 #[derive(Debug)]
 pub struct Rect {
-    x: RangeInclusive<i32>,
-    y: RangeInclusive<i32>,
+    pub x: RangeInclusive<i32>,
+    pub y: RangeInclusive<i32>,
 }
 
 pub fn parse_input(input_data: &str) -> Rect {
@@ -5,10 +5,13 @@ use crate::helpers::{
     image_manipulation::conv2d, input_parsing::parse_as_2d_matrix_with_filled_border,
 };
 
-#[derive(Debug)]
+/// The padding around the input image, large enough for all enhancement rounds.
+pub const BORDER_SIZE: usize = 100;
+
+#[derive(Debug, Clone)]
 pub struct PuzzleInput {
-    enhancement_lookup: Vec<char>,
-    image: Array2<char>,
+    pub enhancement_lookup: Vec<char>,
+    pub image: Array2<char>,
 }
 
 pub fn parse_input(input_data: &str) -> PuzzleInput {
@@ -17,7 +20,7 @@ pub fn parse_input(input_data: &str) -> PuzzleInput {
     let enhancement_lookup = lines.next().unwrap().chars().collect::<Vec<_>>();
     lines.next().unwrap();
 
-    let image = parse_as_2d_matrix_with_filled_border(&lines.join("\n"), 100, '.').unwrap();
+    let image = parse_as_2d_matrix_with_filled_border(&lines.join("\n"), BORDER_SIZE, '.').unwrap();
 
     PuzzleInput {
         enhancement_lookup,
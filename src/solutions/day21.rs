@@ -148,6 +148,12 @@ pub struct UniverseCounter {
     count: [u64; TOTAL_SCORES],
 }
 
+impl Default for UniverseCounter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl UniverseCounter {
     pub fn new() -> Self {
         Self {
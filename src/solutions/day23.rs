@@ -64,7 +64,7 @@ impl Display for HallwayTile {
 
 #[derive(Debug, PartialEq, Eq, Hash, Clone)]
 pub struct Chamber {
-    content: Vec<Option<Amphipod>>,
+    pub content: Vec<Option<Amphipod>>,
 }
 
 impl Chamber {
@@ -84,8 +84,8 @@ impl Chamber {
 
 #[derive(Debug, PartialEq, Eq, Hash, Clone)]
 pub struct GameState {
-    hallway: [HallwayTile; 11],
-    chambers: [Chamber; 4],
+    pub hallway: [HallwayTile; 11],
+    pub chambers: [Chamber; 4],
 }
 
 impl GameState {
@@ -200,9 +200,9 @@ pub fn parse_input(input_data: &str) -> GameState {
 
 #[derive(Eq, PartialEq)]
 pub struct GamePathElement {
-    cost: u32,
-    state: GameState,
-    parent: Option<GameState>,
+    pub cost: u32,
+    pub state: GameState,
+    pub parent: Option<GameState>,
 }
 
 impl Ord for GamePathElement {
@@ -375,6 +375,36 @@ pub fn find_cheapest_solution(
     None
 }
 
+/// Follows the parents of the solution back to the start,
+/// returning all states from the input state to the solved state.
+pub fn solution_path(
+    solution: &GamePathElement,
+    solution_map: &HashMap<GameState, Option<GameState>>,
+) -> Vec<GameState> {
+    let mut path = vec![solution.state.clone()];
+    let mut parent = solution.parent.clone();
+    while let Some(state) = parent {
+        parent = solution_map[&state].clone();
+        path.push(state);
+    }
+    path.reverse();
+    path
+}
+
+/// Inserts the two folded-out rows of the second task into the chambers.
+pub fn unfold_burrow(input_state: &GameState) -> GameState {
+    let mut state = input_state.clone();
+    state.chambers[0].content.insert(1, Some(Amphipod::D));
+    state.chambers[0].content.insert(2, Some(Amphipod::D));
+    state.chambers[1].content.insert(1, Some(Amphipod::C));
+    state.chambers[1].content.insert(2, Some(Amphipod::B));
+    state.chambers[2].content.insert(1, Some(Amphipod::B));
+    state.chambers[2].content.insert(2, Some(Amphipod::A));
+    state.chambers[3].content.insert(1, Some(Amphipod::A));
+    state.chambers[3].content.insert(2, Some(Amphipod::C));
+    state
+}
+
 pub fn task1(input_state: &GameState) -> u32 {
     // let state = input_state.clone();
     // let (_, state) = get_follow_up_states(&state).nth(13).unwrap();
@@ -393,15 +423,7 @@ pub fn task1(input_state: &GameState) -> u32 {
 }
 
 pub fn task2(input_state: &GameState) -> u32 {
-    let mut state = input_state.clone();
-    state.chambers[0].content.insert(1, Some(Amphipod::D));
-    state.chambers[0].content.insert(2, Some(Amphipod::D));
-    state.chambers[1].content.insert(1, Some(Amphipod::C));
-    state.chambers[1].content.insert(2, Some(Amphipod::B));
-    state.chambers[2].content.insert(1, Some(Amphipod::B));
-    state.chambers[2].content.insert(2, Some(Amphipod::A));
-    state.chambers[3].content.insert(1, Some(Amphipod::A));
-    state.chambers[3].content.insert(2, Some(Amphipod::C));
+    let state = unfold_burrow(input_state);
     println!("{}", state);
 
     let (solution, _solution_map) = find_cheapest_solution(&state).unwrap();
@@ -132,6 +132,12 @@ pub enum AluResult {
     NeedsInp(InpQuery),
 }
 
+impl Default for Alu {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl Alu {
     pub fn new() -> Self {
         Self {